@@ -0,0 +1,102 @@
+use std::{sync::Arc, time::Duration};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, SamplingMode};
+use nalgebra::{DMatrix, DVector, DVectorView};
+use rand::{rngs::StdRng, SeedableRng};
+use rand_distr::{Distribution, Normal};
+use sense_motive::{
+    algorithm::{OrthogonalMatchingPursuitSolver, Precomputed, Workspace},
+    signal_utils::{generate_exact_sparse_signal, Amplitude},
+};
+
+// original and compressed lengths
+const SMALL: (usize, usize) = (1024, 256);
+const LARGE: (usize, usize) = (4096, 1024);
+const SEED: u64 = 42;
+
+fn problem(n: usize, m: usize, sparsity: usize) -> (DMatrix<f64>, DVector<f64>) {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let normal = Normal::new(0.0, 1.0 / (m as f64).sqrt()).unwrap();
+    let matrix = DMatrix::from_fn(m, n, |_, _| normal.sample(&mut rng));
+    let original = DVector::from_vec(generate_exact_sparse_signal(
+        n,
+        sparsity,
+        Amplitude::Gaussian,
+        &mut rng,
+    ));
+    let compressed = &matrix * original;
+    (matrix, compressed)
+}
+
+// previous implementation, the selected columns in a matrix of the full size, whose SVD
+// refits all coefficients in each iteration
+fn svd_omp(
+    y: &DVectorView<f64>,
+    matrix: &DMatrix<f64>,
+    max_iter: usize,
+    tolerance: f64,
+) -> DVector<f64> {
+    let mut sparse_solution = DVector::zeros(matrix.ncols());
+    let compressed_signal = y.clone_owned();
+    let mut residual = compressed_signal.clone();
+    let mut selected_column_idxs = Vec::new();
+    let mut selected_basis = DMatrix::zeros(matrix.nrows(), matrix.ncols());
+
+    let max_iter = std::cmp::min(max_iter, matrix.ncols());
+    for _ in 0..max_iter {
+        let inner_products = matrix.tr_mul(&residual);
+        let max_idx = inner_products
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !selected_column_idxs.contains(idx))
+            .map(|(idx, product)| (idx, product.abs()))
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).expect("Can't compare, probably nan"))
+            .map(|(index, _)| index)
+            .unwrap();
+
+        selected_column_idxs.push(max_idx);
+        selected_basis.set_column(max_idx, &matrix.column(max_idx));
+
+        let svd = nalgebra::linalg::SVD::new(selected_basis.clone(), true, true);
+        sparse_solution = svd.solve(&compressed_signal, 0.1).unwrap();
+
+        residual = &compressed_signal - matrix * &sparse_solution;
+        if residual.norm() < tolerance {
+            break;
+        }
+    }
+    sparse_solution
+}
+
+fn omp(c: &mut Criterion) {
+    for (n, m) in [SMALL, LARGE] {
+        let mut group = c.benchmark_group(format!("omp n={n}"));
+        // the previous implementation takes an SVD of an m × n matrix in each iteration, up to
+        // minutes per solve on the larger problem, flat sampling keeps it to a few solves
+        group
+            .sample_size(10)
+            .sampling_mode(SamplingMode::Flat)
+            .warm_up_time(Duration::from_secs(1));
+        for sparsity in [16, 64] {
+            let (matrix, compressed) = problem(n, m, sparsity);
+            let solver = OrthogonalMatchingPursuitSolver::with_parameters(sparsity, 1e-6);
+            let mut workspace = Workspace::with_precomputed(Arc::new(Precomputed::new(&matrix, n)));
+
+            group.bench_with_input(BenchmarkId::new("qr", sparsity), &sparsity, |b, _| {
+                b.iter(|| solver.solve(&compressed.column(0), &matrix))
+            });
+            group.bench_with_input(BenchmarkId::new("qr+gram", sparsity), &sparsity, |b, _| {
+                b.iter(|| {
+                    solver.solve_with_workspace(&compressed.column(0), &matrix, &mut workspace)
+                })
+            });
+            group.bench_with_input(BenchmarkId::new("svd", sparsity), &sparsity, |b, _| {
+                b.iter(|| svd_omp(&compressed.column(0), &matrix, sparsity, 1e-6))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, omp);
+criterion_main!(benches);
@@ -0,0 +1,44 @@
+use rand::{rngs::StdRng, SeedableRng};
+use sense_motive::{
+    entropy_coding::bits_per_sample,
+    quantization::{QuantizationRange, Quantizer},
+    signal_utils::{generate_sparse_in_basis, snr_db, Amplitude},
+    ModelBuilder, Transformation,
+};
+
+const N: usize = 512;
+const M: usize = 128;
+const K: usize = 8;
+const SEED: u64 = 42;
+
+fn main() {
+    let original = generate_sparse_in_basis(
+        N,
+        K,
+        Transformation::Dct1dInverse,
+        Amplitude::Gaussian,
+        &mut StdRng::seed_from_u64(SEED),
+    );
+
+    println!("bits  bits/sample  SNR [dB]");
+    for bits in 2..=10 {
+        let model = ModelBuilder::new()
+            .with_transformation(Transformation::Dct1dInverse)
+            .with_quantizer(Quantizer::Uniform {
+                bits,
+                range: QuantizationRange::Adaptive,
+            })
+            .with_seed(SEED)
+            .build(M, N);
+
+        let encoded = model.compress_encoded(&original);
+        let decompressed = model.decompress_encoded(&encoded).unwrap();
+
+        println!(
+            "{:4}  {:11.3}  {:8.2}",
+            bits,
+            bits_per_sample(&encoded, N),
+            snr_db(&original, &decompressed)
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use std::{fs::File, io::BufWriter};
+
+use sense_motive::phase_transition::{linspace, PhaseTransition};
+
+const N: usize = 64;
+const TRIALS: usize = 20;
+const SEED: u64 = 42;
+
+fn main() -> std::io::Result<()> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "phase_transition.csv".to_string());
+
+    let diagram = PhaseTransition::new(N)
+        .with_grid(linspace(0.05, 1.0, 20), linspace(0.05, 1.0, 20))
+        .with_trials(TRIALS)
+        .with_seed(SEED)
+        .run();
+
+    diagram.write_csv(BufWriter::new(File::create(&path)?))?;
+    println!("Phase diagram written to {}", path);
+    Ok(())
+}
@@ -8,7 +8,10 @@ use rand::{
     distributions::{uniform::SampleUniform, Uniform},
     Rng,
 };
-use sense_motive::{Model, ModelBuilder, Transformation};
+use sense_motive::{
+    signal_utils::{generate_sparse_in_basis, Amplitude},
+    ModelBuilder, Transformation,
+};
 
 const N: usize = 128;
 const M: usize = 64;
@@ -23,11 +26,10 @@ fn main() {
 
     let original = match TRANSFORM {
         Transformation::None => generate_sparse_signal(K),
-         Transformation::Dct1dInverse => generate_cos_signal(K),
-         Transformation::Dct1d => todo!(),
-         Transformation::Fourier1dInverse => todo!(),
-         Transformation::Fourier1d => todo!(),
-     };
+        Transformation::Dct1dInverse => generate_cos_signal(K),
+        // sparse in the basis or dictionary of the transformation
+        _ => generate_sparse_in_basis(N, K, TRANSFORM, Amplitude::Uniform, &mut rand::thread_rng()),
+    };
 
     let compressed = model.compress(&original);
     let decompressed = model.decompress(&compressed);
@@ -56,7 +58,7 @@ fn main() {
     plot.show();
 }
 
-fn error_l2(original: &Vec<f64>, decompressed: &Vec<f64>) -> f64 {
+fn error_l2(original: &[f64], decompressed: &[f64]) -> f64 {
     original
         .iter()
         .zip(decompressed.iter())
@@ -74,7 +76,7 @@ fn generate_cos_signal(sparsity: usize) -> Vec<f64> {
         .map(|i| {
             params
                 .iter()
-                .map(|(f, a)| (a * (i * 2.0 * PI * f).cos()))
+                .map(|(f, a)| a * (i * 2.0 * PI * f).cos())
                 .sum()
         })
         .collect()
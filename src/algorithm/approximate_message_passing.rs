@@ -0,0 +1,306 @@
+use nalgebra::{DMatrix, DVector, DVectorView};
+
+// the quadrature of the state evolution covers ±GRID_LIMIT standard deviations
+const GRID_LIMIT: f64 = 8.0;
+const GRID_POINTS: usize = 161;
+
+/// Prior of the coefficients, zero with probability 1 − rate, otherwise N(0, variance).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BernoulliGaussianPrior {
+    rate: f64,
+    variance: f64,
+}
+
+impl BernoulliGaussianPrior {
+    /// The rate must be strictly between 0 and 1 and the variance positive.
+    pub const fn new(rate: f64, variance: f64) -> BernoulliGaussianPrior {
+        assert!(
+            rate > 0.0 && rate < 1.0,
+            "rate of nonzero coefficients must be between 0 and 1"
+        );
+        assert!(variance > 0.0, "variance must be positive");
+        BernoulliGaussianPrior { rate, variance }
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn variance(&self) -> f64 {
+        self.variance
+    }
+}
+
+/// Scalar denoiser of the AMP iteration, applied to the coefficients plus effective gaussian
+/// noise of standard deviation τ.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Denoiser {
+    /// soft thresholding at alpha·τ
+    SoftThreshold { alpha: f64 },
+    /// posterior mean under the prior, minimizing the mean squared error
+    BernoulliGaussian(BernoulliGaussianPrior),
+}
+
+impl Denoiser {
+    /// Denoised value and its derivative for the effective noise level `tau`.
+    pub fn denoise(&self, value: f64, tau: f64) -> (f64, f64) {
+        match self {
+            Denoiser::SoftThreshold { alpha } => {
+                let threshold = alpha * tau;
+                if value.abs() > threshold {
+                    (value - threshold * value.signum(), 1.0)
+                } else {
+                    (0.0, 0.0)
+                }
+            }
+            Denoiser::BernoulliGaussian(prior) => {
+                // floored, as noiseless measurements (e.g. all zero) have τ = 0
+                let noise = (tau * tau).max(f64::EPSILON * prior.variance);
+                let total = prior.variance + noise;
+                let gain = prior.variance / total;
+
+                // posterior probability of a nonzero coefficient, from the log likelihood ratio
+                let log_ratio = (prior.rate / (1.0 - prior.rate)).ln()
+                    + 0.5 * (noise / total).ln()
+                    + 0.5 * value * value * (1.0 / noise - 1.0 / total);
+                let probability = 1.0 / (1.0 + (-log_ratio).exp());
+                let spread = probability * (1.0 - probability);
+                let probability_derivative = if spread > 0.0 {
+                    spread * value * (1.0 / noise - 1.0 / total)
+                } else {
+                    0.0
+                };
+
+                (
+                    probability * gain * value,
+                    gain * (probability + value * probability_derivative),
+                )
+            }
+        }
+    }
+}
+
+/// Approximate message passing (Donoho–Maleki–Montanari) for gaussian sensing matrices.
+///
+/// Iterates x ← η(x + Aᵀz) with the residual z = y − Ax + (n/m)·z·⟨η'⟩, where the last term
+/// is the Onsager correction. For sensing matrices with iid N(0, 1/m) entries the effective
+/// noise of x + Aᵀz is gaussian, its variance follows the [`state_evolution`]. The sensing
+/// matrix must be real.
+#[derive(Clone, Copy, Debug)]
+pub struct ApproximateMessagePassingSolver {
+    denoiser: Denoiser,
+    max_iter: usize,
+    tolerance: f64,
+}
+
+impl ApproximateMessagePassingSolver {
+    /// Iterates until the relative change of the solution is below `tolerance`.
+    pub fn with_parameters(
+        denoiser: Denoiser,
+        max_iter: usize,
+        tolerance: f64,
+    ) -> ApproximateMessagePassingSolver {
+        ApproximateMessagePassingSolver {
+            denoiser,
+            max_iter,
+            tolerance,
+        }
+    }
+
+    pub fn solve(&self, y: &DVectorView<f64>, sensing_matrix: &DMatrix<f64>) -> DVector<f64> {
+        self.iterates(y, sensing_matrix)
+            .pop()
+            .unwrap_or_else(|| DVector::zeros(sensing_matrix.ncols()))
+    }
+
+    /// Solution after each iteration, e.g. to compare against the state evolution.
+    pub fn iterates(
+        &self,
+        y: &DVectorView<f64>,
+        sensing_matrix: &DMatrix<f64>,
+    ) -> Vec<DVector<f64>> {
+        let (nmeasurements, len) = sensing_matrix.shape();
+        let undersampling = nmeasurements as f64 / len as f64;
+
+        let mut x = DVector::<f64>::zeros(len);
+        let mut z = y.clone_owned();
+        let mut iterates = Vec::new();
+        for _ in 0..self.max_iter {
+            let tau = z.norm() / (nmeasurements as f64).sqrt();
+            let pseudo_data = &x + sensing_matrix.tr_mul(&z);
+
+            let mut derivative_sum = 0.0;
+            let next = pseudo_data.map(|value| {
+                let (denoised, derivative) = self.denoiser.denoise(value, tau);
+                derivative_sum += derivative;
+                denoised
+            });
+            let onsager = derivative_sum / len as f64 / undersampling;
+            z = y - sensing_matrix * &next + z * onsager;
+
+            let change = (&next - &x).norm();
+            let norm = next.norm();
+            x = next;
+            iterates.push(x.clone());
+            if norm > 0.0 && change / norm < self.tolerance {
+                break;
+            }
+        }
+        iterates
+    }
+}
+
+/// Predicted mean squared error per coefficient after each of `iterations` AMP iterations.
+///
+/// `undersampling` is m/n, the effective noise variance follows
+/// τ²ₜ₊₁ = σ² + E[(η(X + τₜZ) − X)²]·n/m with X drawn from the prior and Z ~ N(0, 1).
+pub fn state_evolution(
+    denoiser: Denoiser,
+    prior: BernoulliGaussianPrior,
+    undersampling: f64,
+    noise_variance: f64,
+    iterations: usize,
+) -> Vec<f64> {
+    let grid: Vec<(f64, f64)> = (0..GRID_POINTS)
+        .map(|k| {
+            let z = -GRID_LIMIT + 2.0 * GRID_LIMIT * k as f64 / (GRID_POINTS - 1) as f64;
+            let weight = (-0.5 * z * z).exp() / (2.0 * std::f64::consts::PI).sqrt()
+                * (2.0 * GRID_LIMIT / (GRID_POINTS - 1) as f64);
+            (z, weight)
+        })
+        .collect();
+
+    let mut tau2 = noise_variance + prior.rate * prior.variance / undersampling;
+    let mut mse = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let tau = tau2.sqrt();
+        let zero: f64 = grid
+            .iter()
+            .map(|(z, w)| w * denoiser.denoise(tau * z, tau).0.powi(2))
+            .sum();
+        let nonzero: f64 = grid
+            .iter()
+            .map(|(s, ws)| {
+                let x = prior.variance.sqrt() * s;
+                ws * grid
+                    .iter()
+                    .map(|(z, wz)| wz * (denoiser.denoise(x + tau * z, tau).0 - x).powi(2))
+                    .sum::<f64>()
+            })
+            .sum();
+
+        let error = (1.0 - prior.rate) * zero + prior.rate * nonzero;
+        mse.push(error);
+        tau2 = noise_variance + error / undersampling;
+    }
+    mse
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use nalgebra::{DMatrix, DVector};
+    use rand::{rngs::StdRng, seq::index, Rng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    use super::{
+        state_evolution, ApproximateMessagePassingSolver, BernoulliGaussianPrior, Denoiser,
+    };
+
+    const N: usize = 1000;
+    const M: usize = 500;
+    const PRIOR: BernoulliGaussianPrior = BernoulliGaussianPrior::new(0.1, 1.0);
+    const NOISE_VARIANCE: f64 = 1e-4;
+
+    fn problem() -> (DMatrix<f64>, DVector<f64>, DVector<f64>) {
+        let mut rng = StdRng::seed_from_u64(42);
+        let sensing_matrix = DMatrix::<f64>::from_fn(M, N, |_, _| {
+            let v: f64 = StandardNormal.sample(&mut rng);
+            v / (M as f64).sqrt()
+        });
+        // exactly rate·N nonzeros with the variance of the prior, so that the realization
+        // matches the prior the state evolution is computed for
+        let mut expected = DVector::<f64>::zeros(N);
+        let sparsity = (PRIOR.rate * N as f64) as usize;
+        for idx in index::sample(&mut rng, N, sparsity) {
+            expected[idx] = rng.sample::<f64, _>(StandardNormal);
+        }
+        expected *= (PRIOR.variance * sparsity as f64).sqrt() / expected.norm();
+        let noise = DVector::<f64>::from_fn(M, |_, _| {
+            rng.sample::<f64, _>(StandardNormal) * NOISE_VARIANCE.sqrt()
+        });
+        let compressed = &sensing_matrix * &expected + noise;
+        (sensing_matrix, expected, compressed)
+    }
+
+    #[test]
+    fn denoiser_derivatives() {
+        let h = 1e-6;
+        for denoiser in [
+            Denoiser::SoftThreshold { alpha: 1.5 },
+            Denoiser::BernoulliGaussian(PRIOR),
+        ] {
+            for value in [-2.0, -0.3, 0.7, 3.0] {
+                let numeric = (denoiser.denoise(value + h, 0.5).0
+                    - denoiser.denoise(value - h, 0.5).0)
+                    / (2.0 * h);
+                assert_relative_eq!(denoiser.denoise(value, 0.5).1, numeric, epsilon = 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn bernoulli_gaussian_without_noise() {
+        let denoiser = Denoiser::BernoulliGaussian(PRIOR);
+        for value in [0.0, 1e-3, 2.0, 1e10] {
+            let (denoised, derivative) = denoiser.denoise(value, 0.0);
+            assert!(denoised.is_finite() && derivative.is_finite());
+        }
+        assert_eq!(denoiser.denoise(0.0, 0.0).0, 0.0);
+
+        let (sensing_matrix, _, _) = problem();
+        let algorithm = ApproximateMessagePassingSolver::with_parameters(denoiser, 10, 1e-8);
+        let zero = algorithm.solve(&DVector::<f64>::zeros(M).column(0), &sensing_matrix);
+        assert_eq!(zero, DVector::zeros(N));
+    }
+
+    #[test]
+    #[should_panic(expected = "between 0 and 1")]
+    fn bernoulli_gaussian_rate_is_validated() {
+        BernoulliGaussianPrior::new(1.0, 1.0);
+    }
+
+    #[test]
+    fn follows_state_evolution() {
+        let (sensing_matrix, expected, compressed) = problem();
+        for denoiser in [
+            Denoiser::SoftThreshold { alpha: 1.5 },
+            Denoiser::BernoulliGaussian(PRIOR),
+        ] {
+            let algorithm = ApproximateMessagePassingSolver::with_parameters(denoiser, 15, 0.0);
+            let iterates = algorithm.iterates(&compressed.column(0), &sensing_matrix);
+            let predicted =
+                state_evolution(denoiser, PRIOR, M as f64 / N as f64, NOISE_VARIANCE, 15);
+
+            for (x, mse) in iterates.iter().zip(predicted) {
+                let empirical = (x - &expected).norm_squared() / N as f64;
+                assert!((empirical - mse).abs() < 0.3 * mse + 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn bayes_optimal_denoiser_is_more_accurate() {
+        let (sensing_matrix, expected, compressed) = problem();
+        let error = |denoiser| {
+            let algorithm = ApproximateMessagePassingSolver::with_parameters(denoiser, 50, 1e-8);
+            (algorithm.solve(&compressed.column(0), &sensing_matrix) - &expected).norm()
+                / expected.norm()
+        };
+
+        let soft = error(Denoiser::SoftThreshold { alpha: 1.5 });
+        let bayes = error(Denoiser::BernoulliGaussian(PRIOR));
+        assert!(bayes < soft);
+        assert!(bayes < 0.05);
+    }
+}
@@ -0,0 +1,86 @@
+use nalgebra::ComplexField;
+use num_traits::Zero;
+use simba::scalar::SubsetOf;
+
+use super::thresholding::{hard_threshold, sign};
+use crate::precision::Precision;
+
+/// Binary Iterative Hard Thresholding (BIHT) for 1-bit compressed sensing.
+///
+/// Recovers the direction of a sparse vector from the signs of its measurements,
+/// the result has unit l2 norm.
+#[derive(Clone, Copy, Debug)]
+pub struct BinaryIterativeHardThresholdingSolver {
+    sparsity: usize,
+    max_iter: usize,
+}
+
+impl BinaryIterativeHardThresholdingSolver {
+    pub fn with_parameters(
+        sparsity: usize,
+        max_iter: usize,
+    ) -> BinaryIterativeHardThresholdingSolver {
+        BinaryIterativeHardThresholdingSolver { sparsity, max_iter }
+    }
+
+    pub fn solve<P>(
+        &self,
+        signs: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &nalgebra::DMatrix<P>,
+    ) -> nalgebra::DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let signs: nalgebra::DVector<P> = signs.map(|e| nalgebra::convert(e));
+        let step: P = nalgebra::convert(1.0 / sensing_matrix.nrows() as f64);
+
+        let mut sparse = nalgebra::DVector::<P>::zeros(sensing_matrix.ncols());
+        for _ in 0..self.max_iter {
+            let inconsistency = &signs - (sensing_matrix * &sparse).map(sign);
+            // all signs are consistent with the measurements
+            if sparse.iter().any(|e| !e.is_zero()) && inconsistency.iter().all(|e| e.is_zero()) {
+                break;
+            }
+
+            sparse += sensing_matrix.ad_mul(&inconsistency) * step;
+            hard_threshold(&mut sparse, self.sparsity);
+        }
+
+        let norm = sparse.norm();
+        if norm > <P as ComplexField>::RealField::zero() {
+            sparse.unscale_mut(norm);
+        }
+        sparse
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use nalgebra::{DMatrix, DVector};
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    use super::BinaryIterativeHardThresholdingSolver;
+
+    #[test]
+    fn recovers_direction_of_sparse_vector() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let sensing_matrix =
+            DMatrix::<f64>::from_fn(256, 32, |_, _| StandardNormal.sample(&mut rng));
+
+        let mut expected = DVector::<f64>::zeros(32);
+        expected[3] = 1.0;
+        expected[17] = -2.0;
+        expected[25] = 0.5;
+        let expected = expected.normalize();
+        let signs = (&sensing_matrix * &expected).map(|e| e.signum());
+
+        let algorithm = BinaryIterativeHardThresholdingSolver::with_parameters(3, 100);
+        let decompressed = algorithm.solve(&signs.column(0), &sensing_matrix);
+
+        assert_relative_eq!(decompressed.norm(), 1.0, epsilon = 1e-12);
+        assert!(expected.dot(&decompressed) > 0.95);
+    }
+}
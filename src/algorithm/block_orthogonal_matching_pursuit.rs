@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use crate::precision::Precision;
+use nalgebra::ComplexField;
+use simba::scalar::SubsetOf;
+
+use super::BlockPartition;
+
+/// Block OMP, selects a whole block of columns per iteration.
+///
+/// The block with the largest l2 norm of the correlations with the residual is added to the
+/// support, followed by a least squares fit on all selected blocks.
+#[derive(Clone, Debug)]
+pub struct BlockOrthogonalMatchingPursuitSolver {
+    partition: Arc<BlockPartition>,
+    max_iter: usize,
+    tolerance: f64,
+}
+
+impl BlockOrthogonalMatchingPursuitSolver {
+    pub fn with_parameters(
+        partition: BlockPartition,
+        max_iter: usize,
+        tolerance: f64,
+    ) -> BlockOrthogonalMatchingPursuitSolver {
+        BlockOrthogonalMatchingPursuitSolver {
+            partition: Arc::new(partition),
+            max_iter,
+            tolerance,
+        }
+    }
+
+    pub fn with_tolerance(self, tolerance: f64) -> BlockOrthogonalMatchingPursuitSolver {
+        BlockOrthogonalMatchingPursuitSolver { tolerance, ..self }
+    }
+
+    pub fn solve<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &nalgebra::DMatrix<P>,
+    ) -> nalgebra::DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let compressed_signal: nalgebra::DVector<P> = y.map(|e| nalgebra::convert(e));
+        let mut sparse_solution = nalgebra::DVector::<P>::zeros(sensing_matrix.ncols());
+        let mut residual = compressed_signal.clone();
+        let mut selected_block_idxs = Vec::<usize>::new();
+        let mut selected_column_idxs = Vec::<usize>::new();
+
+        let max_iter = self.max_iter.min(self.partition.len());
+        for _ in 0..max_iter {
+            let inner_products = sensing_matrix.ad_mul(&residual);
+            let max_block = self
+                .partition
+                .blocks()
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| !selected_block_idxs.contains(idx))
+                .map(|(idx, block)| {
+                    let energy: f64 = block
+                        .iter()
+                        .map(|col| {
+                            nalgebra::convert::<_, f64>(inner_products[*col].modulus_squared())
+                        })
+                        .sum();
+                    (idx, energy)
+                })
+                .max_by(|(_, a), (_, b)| a.partial_cmp(b).expect("Can't compare, probably nan"))
+                .map(|(idx, _)| idx)
+                .unwrap();
+
+            selected_block_idxs.push(max_block);
+            selected_column_idxs.extend(&self.partition.blocks()[max_block]);
+            let selected_basis = sensing_matrix.select_columns(&selected_column_idxs);
+
+            let svd = nalgebra::linalg::SVD::new(selected_basis.clone(), true, true);
+            let coefficients = svd
+                .solve(&compressed_signal, nalgebra::convert(f64::EPSILON))
+                .unwrap();
+            for (idx, coefficient) in selected_column_idxs.iter().zip(coefficients.iter()) {
+                sparse_solution[*idx] = *coefficient;
+            }
+
+            residual = &compressed_signal - selected_basis * coefficients;
+            if nalgebra::convert::<_, f64>(residual.norm()) < self.tolerance {
+                break;
+            }
+        }
+
+        sparse_solution
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use nalgebra::{dmatrix, dvector};
+
+    use super::BlockOrthogonalMatchingPursuitSolver;
+    use crate::algorithm::BlockPartition;
+
+    #[test]
+    fn selects_best_matching_block() {
+        let sensing_matrix = dmatrix![
+            1.0, 0.0, 0.6, 0.0;
+            0.0, 1.0, 0.0, 0.6;
+            0.0, 0.0, 0.8, 0.8;
+        ];
+        let expected = dvector![0.0, 0.0, 1.0, 1.0];
+        let compressed = &sensing_matrix * &expected;
+
+        let algorithm = BlockOrthogonalMatchingPursuitSolver::with_parameters(
+            BlockPartition::uniform(4, 2),
+            1,
+            1e-9,
+        );
+        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix);
+
+        assert_relative_eq!(expected, decompressed, epsilon = 1e-9);
+    }
+}
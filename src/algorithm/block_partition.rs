@@ -0,0 +1,82 @@
+/// Partition of the coefficient indices into disjoint blocks (groups).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockPartition {
+    blocks: Vec<Vec<usize>>,
+}
+
+impl BlockPartition {
+    /// Blocks of arbitrary indices, which must be disjoint.
+    pub fn from_blocks(blocks: Vec<Vec<usize>>) -> BlockPartition {
+        let mut indices: Vec<usize> = blocks.iter().flatten().copied().collect();
+        let len = indices.len();
+        indices.sort_unstable();
+        indices.dedup();
+        assert_eq!(indices.len(), len, "blocks must not share indices");
+        BlockPartition { blocks }
+    }
+
+    /// Contiguous blocks of the given sizes.
+    pub fn contiguous(sizes: &[usize]) -> BlockPartition {
+        let mut start = 0;
+        let blocks = sizes
+            .iter()
+            .map(|size| {
+                let block = (start..start + size).collect();
+                start += size;
+                block
+            })
+            .collect();
+        BlockPartition { blocks }
+    }
+
+    /// Contiguous blocks of `block_len` coefficients, the last block may be shorter.
+    pub fn uniform(len: usize, block_len: usize) -> BlockPartition {
+        assert!(block_len > 0, "block length must be positive");
+        let sizes: Vec<usize> = (0..len)
+            .step_by(block_len)
+            .map(|start| block_len.min(len - start))
+            .collect();
+        BlockPartition::contiguous(&sizes)
+    }
+
+    pub fn blocks(&self) -> &[Vec<usize>] {
+        &self.blocks
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BlockPartition;
+
+    #[test]
+    fn uniform_blocks() {
+        let partition = BlockPartition::uniform(7, 3);
+        assert_eq!(partition.blocks(), &[vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+    }
+
+    #[test]
+    fn contiguous_blocks() {
+        let partition = BlockPartition::contiguous(&[1, 3]);
+        assert_eq!(partition.blocks(), &[vec![0], vec![1, 2, 3]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "must not share indices")]
+    fn overlapping_blocks() {
+        BlockPartition::from_blocks(vec![vec![0, 1], vec![1, 2]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "block length must be positive")]
+    fn empty_uniform_blocks() {
+        BlockPartition::uniform(4, 0);
+    }
+}
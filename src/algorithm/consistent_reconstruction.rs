@@ -0,0 +1,115 @@
+use nalgebra::ComplexField;
+use simba::scalar::SubsetOf;
+
+use crate::{precision::Precision, quantization::Cell};
+
+/// Refines a sparse solution, so that its measurements fall into the quantization cells.
+///
+/// Alternates between projecting the measurements of the solution onto the cells and a least
+/// squares fit of these measurements on the support of the solution.
+#[derive(Clone, Copy, Debug)]
+pub struct ConsistentReconstructionSolver {
+    max_iter: usize,
+}
+
+impl ConsistentReconstructionSolver {
+    pub fn with_parameters(max_iter: usize) -> ConsistentReconstructionSolver {
+        ConsistentReconstructionSolver { max_iter }
+    }
+
+    pub fn solve<P>(
+        &self,
+        initial: nalgebra::DVector<P>,
+        cells: &[Cell],
+        sensing_matrix: &nalgebra::DMatrix<P>,
+    ) -> nalgebra::DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let support: Vec<usize> = initial
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| !e.is_zero())
+            .map(|(idx, _)| idx)
+            .collect();
+        if support.is_empty() {
+            return initial;
+        }
+
+        let basis = sensing_matrix.select_columns(&support);
+        let svd = nalgebra::linalg::SVD::new(basis.clone(), true, true);
+
+        let mut coefficients = nalgebra::DVector::from_iterator(
+            support.len(),
+            support.iter().map(|idx| initial[*idx]),
+        );
+        for _ in 0..self.max_iter {
+            let measured = &basis * &coefficients;
+            let mut consistent = true;
+            let projected = nalgebra::DVector::from_iterator(
+                cells.len(),
+                measured.iter().zip(cells.iter()).map(|(m, cell)| {
+                    let real: f64 = nalgebra::convert(m.real());
+                    let clamped = real.clamp(cell.lower, cell.upper);
+                    consistent &= clamped == real;
+                    P::from_subset(&clamped)
+                }),
+            );
+            if consistent {
+                break;
+            }
+            coefficients = svd
+                .solve(&projected, nalgebra::convert(f64::EPSILON))
+                .unwrap();
+        }
+
+        let mut sparse = nalgebra::DVector::<P>::zeros(initial.len());
+        for (idx, coefficient) in support.iter().zip(coefficients.iter()) {
+            sparse[*idx] = *coefficient;
+        }
+        sparse
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use nalgebra::{dmatrix, dvector};
+
+    use super::ConsistentReconstructionSolver;
+    use crate::quantization::Cell;
+
+    #[test]
+    fn measurements_fall_into_cells() {
+        let sensing_matrix = dmatrix![
+            1.0, 0.0, 0.0;
+            0.0, 1.0, 0.0;
+            1.0, 1.0, 0.0;
+        ];
+        let cells = [
+            Cell {
+                lower: 0.9,
+                upper: 1.1,
+            },
+            Cell {
+                lower: 0.9,
+                upper: 1.1,
+            },
+            Cell {
+                lower: 1.5,
+                upper: 1.8,
+            },
+        ];
+
+        let algorithm = ConsistentReconstructionSolver::with_parameters(100);
+        let refined = algorithm.solve(dvector![1.0, 1.0, 0.0], &cells, &sensing_matrix);
+
+        assert_eq!(refined[2], 0.0);
+        let measured = &sensing_matrix * &refined;
+        for (m, cell) in measured.iter().zip(cells.iter()) {
+            assert!(cell.lower - 1e-3 <= *m && *m <= cell.upper + 1e-3);
+        }
+        assert_relative_eq!(refined[0], refined[1], epsilon = 1e-9);
+    }
+}
@@ -0,0 +1,67 @@
+/// Smoothing ε of the weights of reweighted solvers over the outer iterations.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EpsilonSchedule {
+    Fixed(f64),
+    /// multiplied by `factor` after each outer iteration, down to `min`
+    Geometric {
+        initial: f64,
+        factor: f64,
+        min: f64,
+    },
+    /// divided by 10 once the relative change of the solution drops below √ε/100
+    /// (Chartrand–Yin), down to `min`
+    Adaptive {
+        initial: f64,
+        min: f64,
+    },
+}
+
+impl EpsilonSchedule {
+    pub fn initial(&self) -> f64 {
+        match self {
+            EpsilonSchedule::Fixed(epsilon) => *epsilon,
+            EpsilonSchedule::Geometric { initial, .. }
+            | EpsilonSchedule::Adaptive { initial, .. } => *initial,
+        }
+    }
+
+    /// ε of the next outer iteration, given the relative change of the solution in this one.
+    pub fn next(&self, epsilon: f64, relative_change: f64) -> f64 {
+        match self {
+            EpsilonSchedule::Fixed(epsilon) => *epsilon,
+            EpsilonSchedule::Geometric { factor, min, .. } => (epsilon * factor).max(*min),
+            EpsilonSchedule::Adaptive { min, .. } => {
+                if relative_change < epsilon.sqrt() / 100.0 {
+                    (epsilon / 10.0).max(*min)
+                } else {
+                    epsilon
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::EpsilonSchedule;
+
+    #[test]
+    fn schedules() {
+        assert_eq!(EpsilonSchedule::Fixed(0.1).next(0.1, 0.0), 0.1);
+
+        let geometric = EpsilonSchedule::Geometric {
+            initial: 1.0,
+            factor: 0.5,
+            min: 0.3,
+        };
+        assert_eq!(geometric.next(geometric.initial(), 1.0), 0.5);
+        assert_eq!(geometric.next(0.5, 1.0), 0.3);
+
+        let adaptive = EpsilonSchedule::Adaptive {
+            initial: 1.0,
+            min: 1e-8,
+        };
+        assert_eq!(adaptive.next(1.0, 0.1), 1.0);
+        assert_eq!(adaptive.next(1.0, 0.001), 0.1);
+    }
+}
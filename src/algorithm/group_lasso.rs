@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use crate::precision::Precision;
+use nalgebra::ComplexField;
+use simba::scalar::SubsetOf;
+
+use super::{BlockPartition, WarmStart};
+
+/// Group LASSO, minimizes ½‖y − Ax‖² + λ Σ ‖x_g‖ over the groups g of the partition.
+///
+/// Solved by accelerated proximal gradient descent (FISTA) with block soft thresholding,
+/// iterating until the relative change of the solution is below the tolerance.
+#[derive(Clone, Debug)]
+pub struct GroupLassoSolver {
+    partition: Arc<BlockPartition>,
+    lambda: f64,
+    max_iter: usize,
+    tolerance: f64,
+}
+
+impl GroupLassoSolver {
+    pub fn with_parameters(
+        partition: BlockPartition,
+        lambda: f64,
+        max_iter: usize,
+        tolerance: f64,
+    ) -> GroupLassoSolver {
+        GroupLassoSolver {
+            partition: Arc::new(partition),
+            lambda,
+            max_iter,
+            tolerance,
+        }
+    }
+
+    pub fn solve<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &nalgebra::DMatrix<P>,
+    ) -> nalgebra::DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        self.iterate(y, sensing_matrix, None)
+    }
+
+    /// Starts the FISTA iterations from the warm start instead of zero.
+    pub fn solve_warm<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &nalgebra::DMatrix<P>,
+        warm_start: &WarmStart<P>,
+    ) -> nalgebra::DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        self.iterate(y, sensing_matrix, Some(warm_start))
+    }
+
+    fn iterate<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &nalgebra::DMatrix<P>,
+        warm_start: Option<&WarmStart<P>>,
+    ) -> nalgebra::DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let compressed_signal: nalgebra::DVector<P> = y.map(|e| nalgebra::convert(e));
+        let lipschitz = spectral_norm_squared(sensing_matrix);
+        if lipschitz == 0.0 {
+            return nalgebra::DVector::zeros(sensing_matrix.ncols());
+        }
+        let step = 1.0 / lipschitz;
+
+        let mut sparse = match warm_start {
+            Some(warm_start) => warm_start.initial(&compressed_signal, sensing_matrix),
+            None => nalgebra::DVector::<P>::zeros(sensing_matrix.ncols()),
+        };
+        let mut momentum = sparse.clone();
+        let mut t = 1.0_f64;
+        for _ in 0..self.max_iter {
+            let gradient =
+                sensing_matrix.ad_mul(&(sensing_matrix * &momentum - &compressed_signal));
+            let mut next = &momentum - gradient * nalgebra::convert::<f64, P>(step);
+            self.block_soft_threshold(&mut next, self.lambda * step);
+
+            let t_next = (1.0 + (1.0 + 4.0 * t * t).sqrt()) / 2.0;
+            momentum = &next + (&next - &sparse) * nalgebra::convert::<f64, P>((t - 1.0) / t_next);
+            t = t_next;
+
+            let change: f64 = nalgebra::convert((&next - &sparse).norm());
+            let norm: f64 = nalgebra::convert(next.norm());
+            sparse = next;
+            if norm > 0.0 && change / norm < self.tolerance {
+                break;
+            }
+        }
+
+        sparse
+    }
+
+    // shrinks the l2 norm of each block by `threshold`
+    fn block_soft_threshold<P>(&self, vector: &mut nalgebra::DVector<P>, threshold: f64)
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        for block in self.partition.blocks() {
+            let norm: f64 = block
+                .iter()
+                .map(|idx| nalgebra::convert::<_, f64>(vector[*idx].modulus_squared()))
+                .sum::<f64>()
+                .sqrt();
+            let scale = if norm > threshold {
+                1.0 - threshold / norm
+            } else {
+                0.0
+            };
+            for idx in block {
+                vector[*idx] *= nalgebra::convert::<f64, P>(scale);
+            }
+        }
+    }
+}
+
+/// Largest squared singular value, the Lipschitz constant of the gradient of ½‖y − Ax‖².
+pub(crate) fn spectral_norm_squared<P>(matrix: &nalgebra::DMatrix<P>) -> f64
+where
+    P: Precision,
+    <P as ComplexField>::RealField: SubsetOf<f64>,
+{
+    nalgebra::linalg::SVD::new(matrix.clone(), false, false)
+        .singular_values
+        .iter()
+        .map(|s| nalgebra::convert::<_, f64>(s.clone()).powi(2))
+        .fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use nalgebra::{DMatrix, DVector};
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    use super::GroupLassoSolver;
+    use crate::algorithm::BlockPartition;
+
+    #[test]
+    fn recovers_block_sparse_vector() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let sensing_matrix = DMatrix::<f64>::from_fn(16, 32, |_, _| {
+            let v: f64 = StandardNormal.sample(&mut rng);
+            v / 4.0
+        });
+        let mut expected = DVector::<f64>::zeros(32);
+        for idx in 8..12 {
+            expected[idx] = 1.0;
+        }
+        let compressed = &sensing_matrix * &expected;
+
+        let algorithm =
+            GroupLassoSolver::with_parameters(BlockPartition::uniform(32, 4), 1e-4, 5000, 1e-12);
+        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix);
+
+        assert_relative_eq!(expected, decompressed, epsilon = 1e-2);
+        // all blocks outside of the support are zero
+        assert!(decompressed.iter().take(8).all(|e| *e == 0.0));
+    }
+}
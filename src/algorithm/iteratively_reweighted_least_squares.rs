@@ -0,0 +1,206 @@
+use nalgebra::{ComplexField, DMatrix, DVector};
+use simba::scalar::SubsetOf;
+
+use super::EpsilonSchedule;
+use crate::precision::Precision;
+
+// outer iterations stop once the relative change of the solution is below this
+const CONVERGENCE: f64 = 1e-10;
+
+/// Solver for the m×m systems (AQAᴴ)z = y of [`IterativelyReweightedLeastSquaresSolver`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LeastSquaresSolver {
+    Cholesky,
+    /// conjugate gradient, for large systems
+    ConjugateGradient {
+        max_iter: usize,
+        tolerance: f64,
+    },
+}
+
+/// Iteratively reweighted least squares for l_p minimization, p < 1.
+///
+/// Approximates min Σ|xᵢ|ᵖ subject to Ax = y by weighted minimum norm solutions
+/// x = QAᴴ(AQAᴴ)⁻¹y with Qᵢᵢ = (|xᵢ|² + ε)^(1 − p/2) of the previous solution.
+#[derive(Clone, Copy, Debug)]
+pub struct IterativelyReweightedLeastSquaresSolver {
+    p: f64,
+    outer_iter: usize,
+    epsilon: EpsilonSchedule,
+    inner: LeastSquaresSolver,
+}
+
+impl IterativelyReweightedLeastSquaresSolver {
+    pub fn with_parameters(
+        p: f64,
+        outer_iter: usize,
+        epsilon: EpsilonSchedule,
+        inner: LeastSquaresSolver,
+    ) -> IterativelyReweightedLeastSquaresSolver {
+        IterativelyReweightedLeastSquaresSolver {
+            p,
+            outer_iter,
+            epsilon,
+            inner,
+        }
+    }
+
+    pub fn solve<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &DMatrix<P>,
+    ) -> DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let y: DVector<P> = y.map(|e| nalgebra::convert(e));
+        let len = sensing_matrix.ncols();
+        let mut epsilon = self.epsilon.initial();
+
+        // minimum l2 norm solution
+        let mut inverse_weights = DVector::<f64>::repeat(len, 1.0);
+        let mut sparse = DVector::<P>::zeros(len);
+        for _ in 0..self.outer_iter {
+            let Some(next) = self.weighted_minimum_norm(&y, sensing_matrix, &inverse_weights)
+            else {
+                break;
+            };
+
+            let change: f64 = nalgebra::convert((&next - &sparse).norm());
+            let norm: f64 = nalgebra::convert(next.norm());
+            sparse = next;
+            if norm == 0.0 {
+                break;
+            }
+            let relative_change = change / norm;
+            if relative_change < CONVERGENCE {
+                break;
+            }
+
+            epsilon = self.epsilon.next(epsilon, relative_change);
+            inverse_weights = sparse.map(|e| {
+                (nalgebra::convert::<_, f64>(e.modulus_squared()) + epsilon)
+                    .powf(1.0 - self.p / 2.0)
+            });
+        }
+        sparse
+    }
+
+    // QAᴴ(AQAᴴ)⁻¹y, none if the system is singular
+    fn weighted_minimum_norm<P>(
+        &self,
+        y: &DVector<P>,
+        sensing_matrix: &DMatrix<P>,
+        inverse_weights: &DVector<f64>,
+    ) -> Option<DVector<P>>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let mut weighted = sensing_matrix.clone();
+        for (mut column, q) in weighted.column_iter_mut().zip(inverse_weights.iter()) {
+            column *= nalgebra::convert::<f64, P>(*q);
+        }
+        let system = &weighted * sensing_matrix.adjoint();
+
+        let z = match self.inner {
+            LeastSquaresSolver::Cholesky => system.cholesky()?.solve(y),
+            LeastSquaresSolver::ConjugateGradient {
+                max_iter,
+                tolerance,
+            } => conjugate_gradient(&system, y, max_iter, tolerance),
+        };
+        Some(weighted.ad_mul(&z))
+    }
+}
+
+// solves the hermitian positive definite system Mz = b
+fn conjugate_gradient<P>(
+    matrix: &DMatrix<P>,
+    b: &DVector<P>,
+    max_iter: usize,
+    tolerance: f64,
+) -> DVector<P>
+where
+    P: Precision,
+    <P as ComplexField>::RealField: SubsetOf<f64>,
+{
+    let mut z = DVector::<P>::zeros(b.len());
+    let mut residual = b.clone();
+    let mut direction = residual.clone();
+    let mut residual_norm: f64 = nalgebra::convert(residual.norm_squared());
+    let threshold = tolerance * tolerance * residual_norm;
+    for _ in 0..max_iter {
+        if residual_norm <= threshold {
+            break;
+        }
+        let projected = matrix * &direction;
+        let curvature: f64 = nalgebra::convert(direction.dotc(&projected).real());
+        let alpha = nalgebra::convert::<f64, P>(residual_norm / curvature);
+        z += &direction * alpha;
+        residual -= projected * alpha;
+
+        let next_norm: f64 = nalgebra::convert(residual.norm_squared());
+        direction = &residual + direction * nalgebra::convert::<f64, P>(next_norm / residual_norm);
+        residual_norm = next_norm;
+    }
+    z
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use nalgebra::{DMatrix, DVector};
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    use super::{IterativelyReweightedLeastSquaresSolver, LeastSquaresSolver};
+    use crate::algorithm::EpsilonSchedule;
+
+    fn assert_recovers(inner: LeastSquaresSolver) {
+        let mut rng = StdRng::seed_from_u64(42);
+        let sensing_matrix = DMatrix::<f64>::from_fn(24, 64, |_, _| {
+            let v: f64 = StandardNormal.sample(&mut rng);
+            v / 24.0_f64.sqrt()
+        });
+        let mut expected = DVector::<f64>::zeros(64);
+        for (idx, value) in [
+            (3, 1.0),
+            (17, -2.0),
+            (25, 0.5),
+            (40, 1.5),
+            (52, -0.7),
+            (60, 0.3),
+        ] {
+            expected[idx] = value;
+        }
+        let compressed = &sensing_matrix * &expected;
+
+        let algorithm = IterativelyReweightedLeastSquaresSolver::with_parameters(
+            0.5,
+            200,
+            EpsilonSchedule::Adaptive {
+                initial: 1.0,
+                min: 1e-12,
+            },
+            inner,
+        );
+        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix);
+
+        assert_relative_eq!(expected, decompressed, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn cholesky() {
+        assert_recovers(LeastSquaresSolver::Cholesky);
+    }
+
+    #[test]
+    fn conjugate_gradient() {
+        assert_recovers(LeastSquaresSolver::ConjugateGradient {
+            max_iter: 100,
+            tolerance: 1e-12,
+        });
+    }
+}
@@ -0,0 +1,273 @@
+use nalgebra::{ComplexField, DMatrix, DVector};
+use simba::scalar::SubsetOf;
+
+use super::{group_lasso::spectral_norm_squared, reweighted_l1::weighted_lasso};
+use crate::precision::Precision;
+
+/// Kalman filtered compressed sensing (KF-CS, Vaswani) for slowly changing sparse signals.
+///
+/// The coefficients on the tracked support follow a gaussian random walk and are estimated by a
+/// Kalman filter. New coefficients are detected by modified-CS, a LASSO which only penalizes
+/// the coefficients outside of the tracked support, and join the support once their magnitude
+/// exceeds the add threshold. Coefficients whose least squares estimate on the support falls
+/// below the delete threshold leave it again.
+#[derive(Clone, Copy, Debug)]
+pub struct KalmanFilteredCsSolver {
+    lambda: f64,
+    max_iter: usize,
+    add_threshold: f64,
+    delete_threshold: f64,
+    system_variance: f64,
+    noise_variance: f64,
+    initial_variance: f64,
+}
+
+/// Tracked support with the gaussian estimate of its coefficients.
+///
+/// A state belongs to one sensing matrix: the support indexes its columns and the step size
+/// of modified-CS is computed from it at the first step. Start a new state for another matrix.
+#[derive(Clone, Debug)]
+pub struct KalmanState<P>
+where
+    P: Precision,
+{
+    pub support: Vec<usize>,
+    pub mean: DVector<P>,
+    pub covariance: DMatrix<P>,
+    // of the sensing matrix, for the modified-CS step size
+    lipschitz: Option<f64>,
+}
+
+impl<P> KalmanState<P>
+where
+    P: Precision,
+{
+    pub fn new() -> Self {
+        Self {
+            support: Vec::new(),
+            mean: DVector::zeros(0),
+            covariance: DMatrix::zeros(0, 0),
+            lipschitz: None,
+        }
+    }
+
+    /// The estimate as full coefficient vector of length `len`.
+    pub fn coefficients(&self, len: usize) -> DVector<P> {
+        let mut coefficients = DVector::zeros(len);
+        for (idx, mean) in self.support.iter().zip(self.mean.iter()) {
+            coefficients[*idx] = *mean;
+        }
+        coefficients
+    }
+
+    // keeps the entries of the support at the given positions
+    fn retain(&mut self, keep: &[usize]) {
+        self.support = keep.iter().map(|k| self.support[*k]).collect();
+        self.mean = self.mean.select_rows(keep);
+        self.covariance = self.covariance.select_rows(keep).select_columns(keep);
+    }
+}
+
+impl<P> Default for KalmanState<P>
+where
+    P: Precision,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KalmanFilteredCsSolver {
+    /// Modified-CS runs `max_iter` FISTA iterations with the weight `lambda`, thresholds apply
+    /// to the coefficient magnitudes.
+    pub fn with_parameters(
+        lambda: f64,
+        max_iter: usize,
+        add_threshold: f64,
+        delete_threshold: f64,
+    ) -> KalmanFilteredCsSolver {
+        KalmanFilteredCsSolver {
+            lambda,
+            max_iter,
+            add_threshold,
+            delete_threshold,
+            system_variance: 1e-3,
+            noise_variance: 1e-6,
+            initial_variance: 1.0,
+        }
+    }
+
+    /// Variances of the random walk of the coefficients per frame, of the measurement noise
+    /// and of newly detected coefficients around their modified-CS estimate.
+    pub fn with_variances(
+        self,
+        system_variance: f64,
+        noise_variance: f64,
+        initial_variance: f64,
+    ) -> KalmanFilteredCsSolver {
+        KalmanFilteredCsSolver {
+            system_variance,
+            noise_variance,
+            initial_variance,
+            ..self
+        }
+    }
+
+    /// Updates the state with the measurements `y` of the next frame, the state must have been
+    /// updated with the same sensing matrix before, see [`KalmanState`].
+    pub fn step<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &DMatrix<P>,
+        state: &mut KalmanState<P>,
+    ) where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let y: DVector<P> = y.map(|e| nalgebra::convert(e));
+        let len = sensing_matrix.ncols();
+
+        // prediction of the random walk
+        for i in 0..state.support.len() {
+            state.covariance[(i, i)] += nalgebra::convert::<f64, P>(self.system_variance);
+        }
+
+        // modified-CS, detecting additions outside of the tracked support
+        let mut weights = DVector::<f64>::repeat(len, 1.0);
+        for idx in state.support.iter() {
+            weights[*idx] = 0.0;
+        }
+        let initial = state.coefficients(len);
+        let lipschitz = *state
+            .lipschitz
+            .get_or_insert_with(|| spectral_norm_squared(sensing_matrix));
+        let modified = weighted_lasso(
+            &y,
+            sensing_matrix,
+            &weights,
+            self.lambda,
+            self.max_iter,
+            lipschitz,
+            &initial,
+        );
+        let added: Vec<usize> = (0..len)
+            .filter(|idx| {
+                weights[*idx] > 0.0
+                    && nalgebra::convert::<_, f64>(modified[*idx].modulus()) > self.add_threshold
+            })
+            .collect();
+        if !added.is_empty() {
+            let previous = state.support.len();
+            let size = previous + added.len();
+            let mut covariance = DMatrix::zeros(size, size);
+            covariance
+                .view_mut((0, 0), (previous, previous))
+                .copy_from(&state.covariance);
+            for i in previous..size {
+                covariance[(i, i)] = nalgebra::convert(self.initial_variance);
+            }
+            state.covariance = covariance;
+            state.mean = DVector::from_iterator(
+                size,
+                state
+                    .mean
+                    .iter()
+                    .copied()
+                    .chain(added.iter().map(|idx| modified[*idx])),
+            );
+            state.support.extend(added);
+        }
+        if state.support.is_empty() {
+            return;
+        }
+
+        let predicted = state.clone();
+        self.kalman_update(&y, sensing_matrix, state);
+
+        // deletion of vanished coefficients, detected on the least squares estimate on the
+        // support, as the filtered estimate lags behind
+        let basis = sensing_matrix.select_columns(&state.support);
+        let Ok(least_squares) = nalgebra::linalg::SVD::new(basis, true, true)
+            .solve(&y, nalgebra::convert(f64::EPSILON.sqrt()))
+        else {
+            return;
+        };
+        let keep: Vec<usize> = (0..state.support.len())
+            .filter(|i| {
+                nalgebra::convert::<_, f64>(least_squares[*i].modulus()) >= self.delete_threshold
+            })
+            .collect();
+        if keep.len() < state.support.len() {
+            *state = predicted;
+            state.retain(&keep);
+            self.kalman_update(&y, sensing_matrix, state);
+        }
+    }
+
+    fn kalman_update<P>(
+        &self,
+        y: &DVector<P>,
+        sensing_matrix: &DMatrix<P>,
+        state: &mut KalmanState<P>,
+    ) where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let basis = sensing_matrix.select_columns(&state.support);
+        let mut innovation_covariance = &basis * &state.covariance * basis.adjoint();
+        for i in 0..innovation_covariance.nrows() {
+            innovation_covariance[(i, i)] += nalgebra::convert::<f64, P>(self.noise_variance);
+        }
+        let Some(cholesky) = innovation_covariance.cholesky() else {
+            return;
+        };
+        // the covariance is hermitian, so the gain is (S⁻¹ A P)ᴴ
+        let gain = cholesky.solve(&(&basis * &state.covariance)).adjoint();
+        let innovation = y - &basis * &state.mean;
+        state.mean += &gain * innovation;
+        state.covariance = &state.covariance - &gain * &basis * &state.covariance;
+        state.covariance =
+            (&state.covariance + state.covariance.adjoint()) * nalgebra::convert::<f64, P>(0.5);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nalgebra::{DMatrix, DVector};
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, Normal, StandardNormal};
+
+    use super::{KalmanFilteredCsSolver, KalmanState};
+
+    #[test]
+    fn filtering_averages_out_noise() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let sensing_matrix = DMatrix::<f64>::from_fn(32, 64, |_, _| {
+            let v: f64 = StandardNormal.sample(&mut rng);
+            v / 32.0_f64.sqrt()
+        });
+        let mut expected = DVector::<f64>::zeros(64);
+        for (idx, value) in [(3, 1.0), (17, -2.0), (40, 0.5), (63, 1.5)] {
+            expected[idx] = value;
+        }
+
+        // static signal
+        let sigma = 0.05;
+        let noise = Normal::new(0.0, sigma).unwrap();
+        let algorithm = KalmanFilteredCsSolver::with_parameters(1e-3, 200, 0.2, 0.1)
+            .with_variances(0.0, sigma * sigma, 1.0);
+        let mut state = KalmanState::new();
+        let mut errors = Vec::new();
+        for _ in 0..40 {
+            let compressed =
+                &sensing_matrix * &expected + DVector::from_fn(32, |_, _| noise.sample(&mut rng));
+            algorithm.step(&compressed.column(0), &sensing_matrix, &mut state);
+            errors.push((state.coefficients(64) - &expected).norm());
+        }
+
+        let mut support = state.support.clone();
+        support.sort_unstable();
+        assert_eq!(support, vec![3, 17, 40, 63]);
+        assert!(errors[39] < errors[0] / 3.0);
+    }
+}
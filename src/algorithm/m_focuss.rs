@@ -0,0 +1,119 @@
+use crate::precision::Precision;
+use nalgebra::ComplexField;
+use simba::scalar::SubsetOf;
+
+/// M-FOCUSS, a reweighted minimum norm solver for multiple measurement vectors.
+///
+/// Iteratively reweights the columns of the sensing matrix with the l2 norm of the
+/// corresponding coefficient rows, which drives rows outside of the common support to zero.
+#[derive(Clone, Copy, Debug)]
+pub struct MFocussSolver {
+    max_iter: usize,
+    tolerance: f64,
+    // diversity measure exponent, 0 < p <= 1
+    p: f64,
+    // regularization, > 0 for noisy measurements
+    lambda: f64,
+}
+
+impl MFocussSolver {
+    pub fn with_parameters(max_iter: usize, tolerance: f64, p: f64, lambda: f64) -> MFocussSolver {
+        MFocussSolver {
+            max_iter,
+            tolerance,
+            p,
+            lambda,
+        }
+    }
+
+    /// Solves for a row sparse coefficient matrix, `y` holds one channel per column.
+    /// Iterates until the relative change of the solution is below the tolerance.
+    pub fn solve<P>(
+        &self,
+        y: &nalgebra::DMatrix<f64>,
+        sensing_matrix: &nalgebra::DMatrix<P>,
+    ) -> nalgebra::DMatrix<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let compressed: nalgebra::DMatrix<P> = y.map(|e| nalgebra::convert(e));
+        let nrows = sensing_matrix.nrows();
+        let regularization = nalgebra::DMatrix::<P>::identity(nrows, nrows)
+            * nalgebra::convert::<f64, P>(self.lambda);
+
+        let mut sparse = nalgebra::DMatrix::<P>::zeros(sensing_matrix.ncols(), y.ncols());
+        let mut weights = vec![1.0; sensing_matrix.ncols()];
+        for _ in 0..self.max_iter {
+            let mut weighted = sensing_matrix.clone();
+            for (mut col, w) in weighted.column_iter_mut().zip(weights.iter()) {
+                col *= nalgebra::convert::<f64, P>(*w);
+            }
+
+            // minimum norm solution of the weighted problem
+            let gram = &weighted * weighted.adjoint() + &regularization;
+            let solved = match gram.lu().solve(&compressed) {
+                Some(solved) => solved,
+                None => break,
+            };
+            let mut next = weighted.adjoint() * solved;
+            for (mut row, w) in next.row_iter_mut().zip(weights.iter()) {
+                row *= nalgebra::convert::<f64, P>(*w);
+            }
+
+            let change: f64 = nalgebra::convert((&next - &sparse).norm());
+            let norm: f64 = nalgebra::convert(next.norm());
+            sparse = next;
+            if norm == 0.0 || change / norm < self.tolerance {
+                break;
+            }
+
+            weights = sparse
+                .row_iter()
+                .map(|row| nalgebra::convert::<_, f64>(row.norm()).powf(1.0 - self.p / 2.0))
+                .collect();
+        }
+
+        // rows which have been driven to (numerically) zero are outside of the support
+        let max_row: f64 = sparse
+            .row_iter()
+            .map(|row| nalgebra::convert::<_, f64>(row.norm()))
+            .fold(0.0, f64::max);
+        for mut row in sparse.row_iter_mut() {
+            if nalgebra::convert::<_, f64>(row.norm()) < 1e-6 * max_row {
+                row.fill(P::zero());
+            }
+        }
+        sparse
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use nalgebra::DMatrix;
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    use super::MFocussSolver;
+
+    #[test]
+    fn recovers_row_sparse_solution() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let sensing_matrix =
+            DMatrix::<f64>::from_fn(12, 24, |_, _| StandardNormal.sample(&mut rng));
+
+        let mut expected = DMatrix::<f64>::zeros(24, 3);
+        for row in [2, 9, 17] {
+            for col in 0..3 {
+                expected[(row, col)] = StandardNormal.sample(&mut rng);
+            }
+        }
+        let compressed = &sensing_matrix * &expected;
+
+        let algorithm = MFocussSolver::with_parameters(200, 1e-10, 0.8, 1e-12);
+        let decompressed = algorithm.solve(&compressed, &sensing_matrix);
+
+        assert_relative_eq!(expected, decompressed, epsilon = 1e-4);
+    }
+}
@@ -1,6 +1,6 @@
+use super::{Precomputed, Workspace};
 use crate::precision::Precision;
 use nalgebra::ComplexField;
-use num_traits::ToPrimitive;
 use simba::scalar::SubsetOf;
 
 #[derive(Clone, Copy)]
@@ -16,34 +16,68 @@ impl MatchingPursuitSolver {
             tolerance,
         }
     }
+
+    pub fn with_tolerance(self, tolerance: f64) -> MatchingPursuitSolver {
+        MatchingPursuitSolver { tolerance, ..self }
+    }
 }
 
-impl<'a> MatchingPursuitSolver {
+impl MatchingPursuitSolver {
     pub fn solve<P>(
         &self,
         y: &nalgebra::DVectorView<f64>,
         sensing_matrix: &nalgebra::DMatrix<P>,
     ) -> nalgebra::DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        self.solve_with_workspace(y, sensing_matrix, &mut Workspace::new())
+    }
+
+    pub fn solve_with_workspace<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &nalgebra::DMatrix<P>,
+        workspace: &mut Workspace<P>,
+    ) -> nalgebra::DVector<P>
     where
         P: Precision,
         <P as ComplexField>::RealField: SubsetOf<f64>,
     {
         let original_len = sensing_matrix.ncols();
+        workspace.prepare(y.len(), original_len, 0);
+        let precomputed = workspace.precomputed.clone();
+        let gram = precomputed.as_deref().and_then(Precomputed::gram);
 
         let mut sparse = nalgebra::DVector::<P>::zeros(original_len);
-        let mut residual = y.map(|e| nalgebra::convert(e));
+        workspace
+            .residual
+            .zip_apply(y, |e, y| *e = nalgebra::convert(y));
+        workspace.correlate(sensing_matrix);
 
         for _ in 0..self.max_iter {
-            let inner_products = sensing_matrix.tr_mul(&residual);
-            let max_idx = inner_products.icamax();
-            let max_col = sensing_matrix.column(max_idx);
+            let max_idx = workspace.inner_products.icamax();
+            let coefficient = workspace.inner_products[max_idx];
 
-            sparse[max_idx] += inner_products[max_idx];
-            residual -= max_col * inner_products[max_idx];
+            sparse[max_idx] += coefficient;
+            workspace
+                .residual
+                .axpy(-coefficient, &sensing_matrix.column(max_idx), P::one());
 
-            if nalgebra::convert::<_, f64>(residual.norm()) < self.tolerance {
+            if nalgebra::convert::<_, f64>(workspace.residual.norm()) < self.tolerance {
                 break;
             }
+
+            match gram {
+                // Aᴴ(r − a c) = Aᴴr − (AᴴA)[:, idx] c
+                Some(gram) => {
+                    workspace
+                        .inner_products
+                        .axpy(-coefficient, &gram.column(max_idx), P::one())
+                }
+                None => workspace.correlate(sensing_matrix),
+            }
         }
 
         sparse
@@ -68,7 +102,7 @@ mod test {
             0.0, ONE_HALF.sqrt(), 0.0, ONE_THIRD.sqrt();
             0.0, ONE_HALF.sqrt(), 1.0, ONE_THIRD.sqrt();
         ];
-        // TODO SensingMatrix calss should do the normalization maybe (columns need to be normalized, so that l2(column)=1)
+        // columns are normalized (l2(column)=1), as done by the Model for its sensing matrix
 
         let expected = dvector![0.0, 1.0, 0.0, 0.0];
         let compressed = &sensing_matrix * &expected;
@@ -78,7 +112,7 @@ mod test {
             tolerance: 0.1,
         };
 
-        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix.into());
+        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix);
 
         assert_relative_eq!(expected, decompressed);
     }
@@ -98,7 +132,7 @@ mod test {
             tolerance: expected_tolerance + 0.1,
         };
 
-        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix.clone().into());
+        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix.clone());
 
         assert_eq!(decompressed, dvector![1.0, 0.0, 0.0, 0.0]);
 
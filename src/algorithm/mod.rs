@@ -1,22 +1,93 @@
 use nalgebra::ComplexField;
 use simba::scalar::SubsetOf;
 
-use crate::{
-    matrix::{AsVectorChunks, Matrix},
-    precision::Precision,
-};
+use crate::{matrix::AsVectorChunks, precision::Precision};
 
+mod approximate_message_passing;
+mod binary_iterative_hard_thresholding;
+mod block_orthogonal_matching_pursuit;
+mod block_partition;
+mod consistent_reconstruction;
+mod epsilon_schedule;
+mod group_lasso;
+mod iteratively_reweighted_least_squares;
+mod kalman_filtered_cs;
+mod m_focuss;
 mod matching_pursuit;
+mod model_based_cosamp;
+mod model_based_iterative_hard_thresholding;
 mod orthogonal_matching_pursuit;
+mod reweighted_l1;
+mod simultaneous_orthogonal_matching_pursuit;
+mod sparse_bayesian_learning;
+mod thresholding;
+mod total_variation;
+mod tree_approximation;
+mod warm_start;
+mod workspace;
+
+pub use approximate_message_passing::{
+    state_evolution, ApproximateMessagePassingSolver, BernoulliGaussianPrior, Denoiser,
+};
+pub use binary_iterative_hard_thresholding::BinaryIterativeHardThresholdingSolver;
+pub use block_orthogonal_matching_pursuit::BlockOrthogonalMatchingPursuitSolver;
+pub use block_partition::BlockPartition;
+pub use consistent_reconstruction::ConsistentReconstructionSolver;
+pub use epsilon_schedule::EpsilonSchedule;
+pub use group_lasso::GroupLassoSolver;
+pub use iteratively_reweighted_least_squares::{
+    IterativelyReweightedLeastSquaresSolver, LeastSquaresSolver,
+};
+pub use kalman_filtered_cs::{KalmanFilteredCsSolver, KalmanState};
+pub use m_focuss::MFocussSolver;
+pub use matching_pursuit::MatchingPursuitSolver;
+pub use model_based_cosamp::ModelBasedCoSaMPSolver;
+pub use model_based_iterative_hard_thresholding::ModelBasedIterativeHardThresholdingSolver;
+pub use orthogonal_matching_pursuit::OrthogonalMatchingPursuitSolver;
+pub use reweighted_l1::{L1Solver, ReweightedL1Solver};
+pub use simultaneous_orthogonal_matching_pursuit::SimultaneousOrthogonalMatchingPursuitSolver;
+pub use sparse_bayesian_learning::{SparseBayesianEstimate, SparseBayesianLearningSolver};
+pub use total_variation::{SignalShape, TotalVariationSolver};
+pub use tree_approximation::WaveletTree;
+pub(crate) use warm_start::support_of;
+pub use warm_start::WarmStart;
+pub(crate) use workspace::WorkspacePool;
+pub use workspace::{Precomputed, Workspace, DEFAULT_MAX_GRAM_COLUMNS};
 
-#[derive(Clone, Copy)]
+/// Solver used to recover the sparse coefficients.
+///
+/// Not `Copy`, as the block solvers hold their [`BlockPartition`]; clones share it.
+#[derive(Clone)]
 pub enum Algorithm {
-    MatchingPursuit(matching_pursuit::MatchingPursuitSolver),
-    OrthogonalMatchingPursuit(orthogonal_matching_pursuit::OrthogonalMatchingPursuitSolver),
+    MatchingPursuit(MatchingPursuitSolver),
+    OrthogonalMatchingPursuit(OrthogonalMatchingPursuitSolver),
+    BlockOrthogonalMatchingPursuit(BlockOrthogonalMatchingPursuitSolver),
+    GroupLasso(GroupLassoSolver),
+    ModelBasedCoSaMP(ModelBasedCoSaMPSolver),
+    ModelBasedIterativeHardThresholding(ModelBasedIterativeHardThresholdingSolver),
+    ReweightedL1(ReweightedL1Solver),
+    IterativelyReweightedLeastSquares(IterativelyReweightedLeastSquaresSolver),
+    SparseBayesianLearning(SparseBayesianLearningSolver),
+    ApproximateMessagePassing(ApproximateMessagePassingSolver),
 }
 
 impl Algorithm {
     pub fn solve<'a, T, P>(&self, compressed: &'a T, matrix: &nalgebra::DMatrix<P>) -> Vec<P>
+    where
+        T: AsVectorChunks<'a, f64>,
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        self.solve_with_workspace(compressed, matrix, &mut Workspace::new())
+    }
+
+    /// Same as [`Algorithm::solve`], matching pursuit and OMP reuse the buffers of `workspace`.
+    pub fn solve_with_workspace<'a, T, P>(
+        &self,
+        compressed: &'a T,
+        matrix: &nalgebra::DMatrix<P>,
+        workspace: &mut Workspace<P>,
+    ) -> Vec<P>
     where
         T: AsVectorChunks<'a, f64>,
         P: Precision,
@@ -25,26 +96,180 @@ impl Algorithm {
         match self {
             Algorithm::MatchingPursuit(mp) => {
                 let samples_in = matrix.nrows();
-                mp.solve(&compressed.as_vec_chuncks(samples_in), matrix)
+                mp.solve_with_workspace(&compressed.as_vec_chuncks(samples_in), matrix, workspace)
                     .data
                     .into()
             }
             Algorithm::OrthogonalMatchingPursuit(omp) => {
                 let samples_in = matrix.nrows();
-                omp.solve(&compressed.as_vec_chuncks(samples_in), matrix)
+                omp.solve_with_workspace(&compressed.as_vec_chuncks(samples_in), matrix, workspace)
                     .data
                     .into()
             }
+            Algorithm::BlockOrthogonalMatchingPursuit(bomp) => {
+                let samples_in = matrix.nrows();
+                bomp.solve(&compressed.as_vec_chuncks(samples_in), matrix)
+                    .data
+                    .into()
+            }
+            Algorithm::GroupLasso(lasso) => {
+                let samples_in = matrix.nrows();
+                lasso
+                    .solve(&compressed.as_vec_chuncks(samples_in), matrix)
+                    .data
+                    .into()
+            }
+            Algorithm::ModelBasedCoSaMP(cosamp) => {
+                let samples_in = matrix.nrows();
+                cosamp
+                    .solve(&compressed.as_vec_chuncks(samples_in), matrix)
+                    .data
+                    .into()
+            }
+            Algorithm::ModelBasedIterativeHardThresholding(iht) => {
+                let samples_in = matrix.nrows();
+                iht.solve(&compressed.as_vec_chuncks(samples_in), matrix)
+                    .data
+                    .into()
+            }
+            Algorithm::ReweightedL1(l1) => {
+                let samples_in = matrix.nrows();
+                l1.solve(&compressed.as_vec_chuncks(samples_in), matrix)
+                    .data
+                    .into()
+            }
+            Algorithm::IterativelyReweightedLeastSquares(irls) => {
+                let samples_in = matrix.nrows();
+                irls.solve(&compressed.as_vec_chuncks(samples_in), matrix)
+                    .data
+                    .into()
+            }
+            Algorithm::SparseBayesianLearning(sbl) => {
+                let samples_in = matrix.nrows();
+                sbl.solve(&compressed.as_vec_chuncks(samples_in), matrix)
+                    .mean
+                    .data
+                    .into()
+            }
+            Algorithm::ApproximateMessagePassing(amp) => {
+                let samples_in = matrix.nrows();
+                let real = matrix.map(|e| {
+                    nalgebra::try_convert::<P, f64>(e).expect("AMP needs a real sensing matrix")
+                });
+                amp.solve(&compressed.as_vec_chuncks(samples_in), &real)
+                    .iter()
+                    .map(|e| P::from_subset(e))
+                    .collect()
+            }
+        }
+    }
+}
+
+impl Algorithm {
+    /// Same as [`Algorithm::solve_with_workspace`], starting from a previous solution or support.
+    ///
+    /// Used by OMP, model-based IHT, group LASSO and reweighted l1 with FISTA, the other
+    /// algorithms solve from scratch.
+    pub fn solve_warm<'a, T, P>(
+        &self,
+        compressed: &'a T,
+        matrix: &nalgebra::DMatrix<P>,
+        workspace: &mut Workspace<P>,
+        warm_start: &WarmStart<P>,
+    ) -> Vec<P>
+    where
+        T: AsVectorChunks<'a, f64>,
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let samples_in = matrix.nrows();
+        let y = compressed.as_vec_chuncks(samples_in);
+        match self {
+            Algorithm::OrthogonalMatchingPursuit(omp) => omp
+                .solve_warm(&y, matrix, workspace, warm_start)
+                .data
+                .into(),
+            Algorithm::ModelBasedIterativeHardThresholding(iht) => {
+                iht.solve_warm(&y, matrix, warm_start).data.into()
+            }
+            Algorithm::GroupLasso(lasso) => lasso.solve_warm(&y, matrix, warm_start).data.into(),
+            Algorithm::ReweightedL1(l1) => l1.solve_warm(&y, matrix, warm_start).data.into(),
+            _ => self.solve_with_workspace(compressed, matrix, workspace),
+        }
+    }
+}
+
+impl Algorithm {
+    /// Same algorithm, stopping once the residual norm is below `tolerance`.
+    ///
+    /// Group LASSO balances the residual through its regularization instead, reweighted l1,
+    /// IRLS, sparse Bayesian learning and AMP stop on convergence of their iterates; these are
+    /// unchanged.
+    pub fn with_tolerance(&self, tolerance: f64) -> Algorithm {
+        match self {
+            Algorithm::MatchingPursuit(mp) => {
+                Algorithm::MatchingPursuit(mp.with_tolerance(tolerance))
+            }
+            Algorithm::OrthogonalMatchingPursuit(omp) => {
+                Algorithm::OrthogonalMatchingPursuit(omp.with_tolerance(tolerance))
+            }
+            Algorithm::BlockOrthogonalMatchingPursuit(bomp) => {
+                Algorithm::BlockOrthogonalMatchingPursuit(bomp.clone().with_tolerance(tolerance))
+            }
+            Algorithm::GroupLasso(_)
+            | Algorithm::ReweightedL1(_)
+            | Algorithm::IterativelyReweightedLeastSquares(_)
+            | Algorithm::SparseBayesianLearning(_)
+            | Algorithm::ApproximateMessagePassing(_) => self.clone(),
+            Algorithm::ModelBasedCoSaMP(cosamp) => {
+                Algorithm::ModelBasedCoSaMP(cosamp.with_tolerance(tolerance))
+            }
+            Algorithm::ModelBasedIterativeHardThresholding(iht) => {
+                Algorithm::ModelBasedIterativeHardThresholding(iht.with_tolerance(tolerance))
+            }
         }
     }
 }
 
 impl Default for Algorithm {
     fn default() -> Self {
-        Algorithm::OrthogonalMatchingPursuit(
-            orthogonal_matching_pursuit::OrthogonalMatchingPursuitSolver::with_parameters(
-                1000, 0.1,
-            ),
+        Algorithm::OrthogonalMatchingPursuit(OrthogonalMatchingPursuitSolver::with_parameters(
+            1000, 0.1,
+        ))
+    }
+}
+
+/// Solvers for multiple measurement vectors, recovering a row sparse coefficient matrix.
+#[derive(Clone, Copy, Debug)]
+pub enum MultipleMeasurementAlgorithm {
+    SimultaneousOrthogonalMatchingPursuit(SimultaneousOrthogonalMatchingPursuitSolver),
+    MFocuss(MFocussSolver),
+}
+
+impl MultipleMeasurementAlgorithm {
+    /// `compressed` holds one channel per column.
+    pub fn solve<P>(
+        &self,
+        compressed: &nalgebra::DMatrix<f64>,
+        matrix: &nalgebra::DMatrix<P>,
+    ) -> nalgebra::DMatrix<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        match self {
+            MultipleMeasurementAlgorithm::SimultaneousOrthogonalMatchingPursuit(somp) => {
+                somp.solve(compressed, matrix)
+            }
+            MultipleMeasurementAlgorithm::MFocuss(focuss) => focuss.solve(compressed, matrix),
+        }
+    }
+}
+
+impl Default for MultipleMeasurementAlgorithm {
+    fn default() -> Self {
+        MultipleMeasurementAlgorithm::SimultaneousOrthogonalMatchingPursuit(
+            SimultaneousOrthogonalMatchingPursuitSolver::with_parameters(1000, 0.1),
         )
     }
 }
@@ -0,0 +1,129 @@
+use crate::precision::Precision;
+use nalgebra::ComplexField;
+use simba::scalar::SubsetOf;
+
+use super::{tree_approximation::tree_approximate, WaveletTree};
+
+/// Model-based CoSaMP, restricting the solution to tree sparse wavelet coefficients.
+///
+/// The hard thresholding steps of CoSaMP are replaced by the projection onto rooted subtrees
+/// of `sparsity` nodes, which needs fewer measurements for signals with tree structure.
+#[derive(Clone, Copy, Debug)]
+pub struct ModelBasedCoSaMPSolver {
+    tree: WaveletTree,
+    sparsity: usize,
+    max_iter: usize,
+    tolerance: f64,
+}
+
+impl ModelBasedCoSaMPSolver {
+    pub fn with_parameters(
+        tree: WaveletTree,
+        sparsity: usize,
+        max_iter: usize,
+        tolerance: f64,
+    ) -> ModelBasedCoSaMPSolver {
+        ModelBasedCoSaMPSolver {
+            tree,
+            sparsity,
+            max_iter,
+            tolerance,
+        }
+    }
+
+    pub fn with_tolerance(self, tolerance: f64) -> ModelBasedCoSaMPSolver {
+        ModelBasedCoSaMPSolver { tolerance, ..self }
+    }
+
+    pub fn solve<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &nalgebra::DMatrix<P>,
+    ) -> nalgebra::DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let compressed_signal: nalgebra::DVector<P> = y.map(|e| nalgebra::convert(e));
+        let mut sparse = nalgebra::DVector::<P>::zeros(sensing_matrix.ncols());
+        let mut residual = compressed_signal.clone();
+
+        for _ in 0..self.max_iter {
+            // identify a subtree of twice the sparsity in the proxy and merge it with the support
+            let mut proxy = sensing_matrix.ad_mul(&residual);
+            tree_approximate(&mut proxy, self.tree, 2 * self.sparsity);
+            let support: Vec<usize> = (0..sparse.len())
+                .filter(|idx| !proxy[*idx].is_zero() || !sparse[*idx].is_zero())
+                .collect();
+            if support.is_empty() {
+                break;
+            }
+
+            let svd =
+                nalgebra::linalg::SVD::new(sensing_matrix.select_columns(&support), true, true);
+            let coefficients = svd
+                .solve(&compressed_signal, nalgebra::convert(f64::EPSILON))
+                .unwrap();
+            let mut estimate = nalgebra::DVector::<P>::zeros(sparse.len());
+            for (idx, coefficient) in support.iter().zip(coefficients.iter()) {
+                estimate[*idx] = *coefficient;
+            }
+            tree_approximate(&mut estimate, self.tree, self.sparsity);
+
+            let next_residual = &compressed_signal - sensing_matrix * &estimate;
+            let residual_norm: f64 = nalgebra::convert(next_residual.norm());
+            let previous_norm: f64 = nalgebra::convert(residual.norm());
+            // no further improvement
+            if residual_norm >= previous_norm {
+                break;
+            }
+            sparse = estimate;
+            residual = next_residual;
+            if residual_norm < self.tolerance {
+                break;
+            }
+        }
+
+        sparse
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use nalgebra::{DMatrix, DVector};
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    use super::ModelBasedCoSaMPSolver;
+    use crate::algorithm::WaveletTree;
+
+    #[test]
+    fn recovers_tree_sparse_vector() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let sensing_matrix = DMatrix::<f64>::from_fn(24, 64, |_, _| {
+            let v: f64 = StandardNormal.sample(&mut rng);
+            v / 24.0_f64.sqrt()
+        });
+        // path from the root to the leaf 45 plus the sibling 3
+        let mut expected = DVector::<f64>::zeros(64);
+        for (idx, value) in [
+            (0, 2.0),
+            (1, -1.0),
+            (2, 1.5),
+            (3, 0.5),
+            (5, 1.0),
+            (11, -0.8),
+            (22, 0.6),
+            (45, 1.2),
+        ] {
+            expected[idx] = value;
+        }
+        let compressed = &sensing_matrix * &expected;
+
+        let algorithm = ModelBasedCoSaMPSolver::with_parameters(WaveletTree::Binary, 8, 50, 1e-9);
+        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix);
+
+        assert_relative_eq!(expected, decompressed, epsilon = 1e-9);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::precision::Precision;
+use nalgebra::ComplexField;
+use simba::scalar::SubsetOf;
+
+use super::{tree_approximation::tree_approximate, WarmStart, WaveletTree};
+
+/// Model-based iterative hard thresholding, a gradient step on ½‖y − Ax‖² followed by the
+/// projection onto rooted subtrees of `sparsity` nodes.
+///
+/// The step size is chosen as in normalized IHT, optimal for the gradient restricted to the
+/// current support.
+#[derive(Clone, Copy, Debug)]
+pub struct ModelBasedIterativeHardThresholdingSolver {
+    tree: WaveletTree,
+    sparsity: usize,
+    max_iter: usize,
+    tolerance: f64,
+}
+
+impl ModelBasedIterativeHardThresholdingSolver {
+    pub fn with_parameters(
+        tree: WaveletTree,
+        sparsity: usize,
+        max_iter: usize,
+        tolerance: f64,
+    ) -> ModelBasedIterativeHardThresholdingSolver {
+        ModelBasedIterativeHardThresholdingSolver {
+            tree,
+            sparsity,
+            max_iter,
+            tolerance,
+        }
+    }
+
+    pub fn with_tolerance(self, tolerance: f64) -> ModelBasedIterativeHardThresholdingSolver {
+        ModelBasedIterativeHardThresholdingSolver { tolerance, ..self }
+    }
+
+    pub fn solve<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &nalgebra::DMatrix<P>,
+    ) -> nalgebra::DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        self.iterate(y, sensing_matrix, None)
+    }
+
+    /// Starts from the tree projection of the warm start instead of zero.
+    pub fn solve_warm<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &nalgebra::DMatrix<P>,
+        warm_start: &WarmStart<P>,
+    ) -> nalgebra::DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        self.iterate(y, sensing_matrix, Some(warm_start))
+    }
+
+    fn iterate<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &nalgebra::DMatrix<P>,
+        warm_start: Option<&WarmStart<P>>,
+    ) -> nalgebra::DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let compressed_signal: nalgebra::DVector<P> = y.map(|e| nalgebra::convert(e));
+        let mut sparse = match warm_start {
+            Some(warm_start) => {
+                let mut initial = warm_start.initial(&compressed_signal, sensing_matrix);
+                tree_approximate(&mut initial, self.tree, self.sparsity);
+                initial
+            }
+            None => nalgebra::DVector::<P>::zeros(sensing_matrix.ncols()),
+        };
+
+        for _ in 0..self.max_iter {
+            let residual = &compressed_signal - sensing_matrix * &sparse;
+            if nalgebra::convert::<_, f64>(residual.norm()) < self.tolerance {
+                break;
+            }
+            let gradient = sensing_matrix.ad_mul(&residual);
+
+            // restrict the gradient to the support, initially to the best subtree of it
+            let mut restricted = gradient.clone();
+            if sparse.iter().all(|e| e.is_zero()) {
+                tree_approximate(&mut restricted, self.tree, self.sparsity);
+            } else {
+                restricted.zip_apply(&sparse, |g, s| {
+                    if s.is_zero() {
+                        *g = P::zero()
+                    }
+                });
+            }
+            let numerator: f64 = nalgebra::convert(restricted.norm_squared());
+            let denominator: f64 = nalgebra::convert((sensing_matrix * &restricted).norm_squared());
+            if denominator == 0.0 {
+                break;
+            }
+            let step: P = nalgebra::convert(numerator / denominator);
+
+            sparse += gradient * step;
+            tree_approximate(&mut sparse, self.tree, self.sparsity);
+        }
+
+        sparse
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nalgebra::{DMatrix, DVector};
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    use super::ModelBasedIterativeHardThresholdingSolver;
+    use crate::algorithm::WaveletTree;
+
+    #[test]
+    fn recovers_tree_sparse_vector() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let sensing_matrix = DMatrix::<f64>::from_fn(32, 64, |_, _| {
+            let v: f64 = StandardNormal.sample(&mut rng);
+            v / 32.0_f64.sqrt()
+        });
+        let mut expected = DVector::<f64>::zeros(64);
+        for (idx, value) in [(0, 2.0), (1, -1.0), (2, 1.5), (5, 1.0), (11, -0.8)] {
+            expected[idx] = value;
+        }
+        let compressed = &sensing_matrix * &expected;
+
+        let algorithm = ModelBasedIterativeHardThresholdingSolver::with_parameters(
+            WaveletTree::Binary,
+            5,
+            5000,
+            1e-9,
+        );
+        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix);
+
+        assert!((expected - decompressed).norm() < 1e-6);
+    }
+}
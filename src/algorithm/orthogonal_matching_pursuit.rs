@@ -1,6 +1,7 @@
+use super::{Precomputed, WarmStart, Workspace};
 use crate::precision::Precision;
 use nalgebra::ComplexField;
-use simba::scalar::{SubsetOf, SupersetOf};
+use simba::scalar::SubsetOf;
 
 #[derive(Clone, Copy)]
 pub struct OrthogonalMatchingPursuitSolver {
@@ -15,9 +16,13 @@ impl OrthogonalMatchingPursuitSolver {
             tolerance,
         }
     }
+
+    pub fn with_tolerance(self, tolerance: f64) -> OrthogonalMatchingPursuitSolver {
+        OrthogonalMatchingPursuitSolver { tolerance, ..self }
+    }
 }
 
-impl<'a> OrthogonalMatchingPursuitSolver {
+impl OrthogonalMatchingPursuitSolver {
     pub fn solve<P>(
         &self,
         // TODO should y also be type of P? => convert earlier
@@ -28,54 +33,195 @@ impl<'a> OrthogonalMatchingPursuitSolver {
         P: Precision,
         <P as ComplexField>::RealField: SubsetOf<f64>,
     {
-        let original_len = sensing_matrix.ncols();
+        self.solve_with_workspace(y, sensing_matrix, &mut Workspace::new())
+    }
 
-        let mut sparse_solution = nalgebra::DVector::<P>::zeros(original_len);
-        let mut compressed_signal = y.map(|e| nalgebra::convert(e));
-        let mut residual = compressed_signal.clone();
-        let mut selected_column_idxs = Vec::<usize>::new();
-
-        // TODO this is actually sparse_solution, try the nalgebra type
-        let mut selected_basis =
-            nalgebra::DMatrix::zeros(sensing_matrix.nrows(), sensing_matrix.ncols());
-
-        let max_iter = std::cmp::min(self.max_iter, sensing_matrix.ncols());
-        for _ in 0..max_iter {
-            let inner_products: nalgebra::DVector<P> = sensing_matrix.tr_mul(&residual);
-
-            // filter out already used indices
-            //TODO : function to find max idx/ product
-            let view: nalgebra::DMatrixView<P> = inner_products.as_view();
-            let filter_view = view
-                .iter()
-                .enumerate()
-                .filter(|(idx, product)| !selected_column_idxs.contains(&idx));
-            let max_idx = filter_view
-                .map(|(idx, product)| (idx, product.norm1()))
-                .max_by(|(_, a), (_, b)| a.partial_cmp(b).expect("Can't compare, probably nan"))
-                .map(|(index, _)| index)
-                .unwrap();
-
-            // TODO handle None
-            // update support
-            selected_column_idxs.push(max_idx);
-            let selected_column = sensing_matrix.column(max_idx);
-            selected_basis.set_column(max_idx, &selected_column);
+    pub fn solve_with_workspace<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &nalgebra::DMatrix<P>,
+        workspace: &mut Workspace<P>,
+    ) -> nalgebra::DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        self.solve_from(y, sensing_matrix, workspace, Vec::new())
+    }
+
+    /// Selects the columns of the warm start support first, as long as they are linearly
+    /// independent, and continues greedily from there.
+    ///
+    /// The warm start support doesn't count against `max_iter`: the pursuit gets that many
+    /// iterations on top of it, then atoms with negligible coefficients are pruned, e.g. stale
+    /// ones of a previous frame, and the `max_iter` largest are refitted.
+    pub fn solve_warm<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &nalgebra::DMatrix<P>,
+        workspace: &mut Workspace<P>,
+        warm_start: &WarmStart<P>,
+    ) -> nalgebra::DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let mut support = warm_start.support();
+        support.retain(|idx| *idx < sensing_matrix.ncols());
+        let mut seen = std::collections::HashSet::new();
+        support.retain(|idx| seen.insert(*idx));
+        if support.is_empty() {
+            return self.solve_from(y, sensing_matrix, workspace, support);
+        }
 
-            // Least square
-            let svd = nalgebra::linalg::SVD::new(selected_basis.clone(), true, true);
-            let eps = 0.1; // TODO make configurable
-            sparse_solution = svd.solve(&residual, nalgebra::convert(eps)).unwrap();
+        let extended = OrthogonalMatchingPursuitSolver {
+            max_iter: self.max_iter + support.len(),
+            ..*self
+        }
+        .solve_from(y, sensing_matrix, workspace, support);
+        let mut coefficients: Vec<(usize, f64)> = extended
+            .iter()
+            .enumerate()
+            .map(|(idx, coefficient)| (idx, nalgebra::convert(coefficient.modulus())))
+            .collect();
+        let largest = coefficients.iter().map(|(_, c)| *c).fold(0.0, f64::max);
+        coefficients.retain(|(_, c)| *c > f64::EPSILON.sqrt() * largest);
+        coefficients
+            .sort_by(|(_, a), (_, b)| b.partial_cmp(a).expect("Can't compare, probably nan"));
+        coefficients.truncate(self.max_iter);
 
-            // calculate residual
-            residual = &compressed_signal - (sensing_matrix * &sparse_solution);
+        let pruned = coefficients.into_iter().map(|(idx, _)| idx).collect();
+        self.solve_from(y, sensing_matrix, workspace, pruned)
+    }
+
+    /// Keeps a QR factorization of the selected columns, which is extended by one column per
+    /// iteration instead of refitting all coefficients. With a precomputed Gram matrix the
+    /// correlations are updated from the coefficients, without multiplying the sensing matrix.
+    fn solve_from<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &nalgebra::DMatrix<P>,
+        workspace: &mut Workspace<P>,
+        forced: Vec<usize>,
+    ) -> nalgebra::DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let original_len = sensing_matrix.ncols();
+        let max_iter = std::cmp::min(self.max_iter, sensing_matrix.ncols().min(y.len()));
+        workspace.prepare(y.len(), original_len, max_iter);
+        workspace
+            .residual
+            .zip_apply(y, |e, y| *e = nalgebra::convert(y));
+        let precomputed = workspace.precomputed.clone();
+        let gram = precomputed.as_deref().and_then(Precomputed::gram);
+        if gram.is_some() {
+            workspace.correlate(sensing_matrix);
+            workspace.adjoint_y.copy_from(&workspace.inner_products);
+        }
+        let mut forced = forced.into_iter();
+        // columns found to be in the span of the basis, they stay there as the basis grows
+        let mut excluded = std::collections::HashSet::new();
 
-            // abort
-            if nalgebra::convert::<_, f64>(residual.norm()) < self.tolerance {
+        while workspace.support.len() < max_iter {
+            if nalgebra::convert::<_, f64>(workspace.residual.norm()) < self.tolerance {
                 break;
             }
+            let k = workspace.support.len();
+
+            let forced_idx = forced.next();
+            let max_idx = match forced_idx {
+                Some(idx) => idx,
+                None => {
+                    match gram {
+                        // Aᴴr = Aᴴy − AᴴA x, with the coefficients x of the current support
+                        Some(gram) => {
+                            let coefficients = workspace
+                                .r
+                                .view((0, 0), (k, k))
+                                .solve_upper_triangular(&workspace.projections.rows(0, k))
+                                .expect("diagonal of R is non zero");
+                            workspace.inner_products.copy_from(&workspace.adjoint_y);
+                            for (idx, coefficient) in
+                                workspace.support.iter().zip(coefficients.iter())
+                            {
+                                workspace.inner_products.axpy(
+                                    -*coefficient,
+                                    &gram.column(*idx),
+                                    P::one(),
+                                );
+                            }
+                        }
+                        None => workspace.correlate(sensing_matrix),
+                    }
+                    match workspace
+                        .inner_products
+                        .iter()
+                        .enumerate()
+                        .filter(|(idx, _)| {
+                            !workspace.support.contains(idx) && !excluded.contains(idx)
+                        })
+                        .map(|(idx, product)| (idx, product.modulus()))
+                        .max_by(|(_, a), (_, b)| {
+                            a.partial_cmp(b).expect("Can't compare, probably nan")
+                        })
+                        .map(|(index, _)| index)
+                    {
+                        Some(idx) => idx,
+                        None => break,
+                    }
+                }
+            };
+
+            let Workspace {
+                residual,
+                q,
+                r,
+                projections,
+                support: selected_column_idxs,
+                ..
+            } = &mut *workspace;
+
+            // Gram-Schmidt of the new column against the basis, repeated once for stability
+            let column = sensing_matrix.column(max_idx);
+            let basis = q.columns(0, k);
+            let mut coefficients = basis.ad_mul(&column);
+            let mut orthogonal = column - basis * &coefficients;
+            let correction = basis.ad_mul(&orthogonal);
+            orthogonal -= basis * &correction;
+            coefficients += correction;
+
+            let diagonal: f64 = nalgebra::convert(orthogonal.norm());
+            let column_norm: f64 = nalgebra::convert(column.norm());
+            if diagonal <= f64::EPSILON.sqrt() * column_norm.max(f64::MIN_POSITIVE) {
+                // the column is (numerically) in the span of the basis, or all zeros
+                excluded.insert(max_idx);
+                continue;
+            }
+            orthogonal.unscale_mut(nalgebra::convert(diagonal));
+
+            selected_column_idxs.push(max_idx);
+            r.view_mut((0, k), (k, 1)).copy_from(&coefficients);
+            r[(k, k)] = nalgebra::convert(diagonal);
+            // Qᴴy, the residual is orthogonal to the previous basis columns
+            projections[k] = orthogonal.dotc(residual);
+            residual.axpy(-projections[k], &orthogonal, P::one());
+            q.set_column(k, &orthogonal);
         }
 
+        // back substitution of R x = Qᴴy
+        let k = workspace.support.len();
+        let coefficients = workspace
+            .r
+            .view((0, 0), (k, k))
+            .solve_upper_triangular(&workspace.projections.rows(0, k))
+            .expect("diagonal of R is non zero");
+
+        let mut sparse_solution = nalgebra::DVector::<P>::zeros(original_len);
+        for (idx, coefficient) in workspace.support.iter().zip(coefficients.iter()) {
+            sparse_solution[*idx] = *coefficient;
+        }
         sparse_solution
     }
 }
@@ -87,6 +233,7 @@ mod test {
     use nalgebra::{dmatrix, dvector};
 
     use super::OrthogonalMatchingPursuitSolver;
+    use crate::algorithm::{WarmStart, Workspace};
 
     const ONE_HALF: f64 = 1.0 / 2.0;
     const ONE_THIRD: f64 = 1.0 / 3.0;
@@ -98,7 +245,7 @@ mod test {
             0.0, ONE_HALF.sqrt(), 0.0, ONE_THIRD.sqrt();
             0.0, ONE_HALF.sqrt(), 1.0, ONE_THIRD.sqrt();
         ];
-        // TODO SensingMatrix calss should do the normalization maybe (columns need to be normalized, so that l2(column)=1)
+        // columns are normalized (l2(column)=1), as done by the Model for its sensing matrix
 
         let expected = dvector![0.0, 1.0, 0.0, 0.0];
         let compressed = &sensing_matrix * &expected;
@@ -108,7 +255,7 @@ mod test {
             tolerance: 0.1,
         };
 
-        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix.into());
+        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix);
 
         assert_relative_eq!(expected, decompressed);
     }
@@ -128,12 +275,120 @@ mod test {
             tolerance: expected_tolerance + 0.1,
         };
 
-        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix.clone().into());
+        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix.clone());
 
-        assert_eq!(decompressed, dvector![1.0, 0.0, 0.0, 0.0]);
+        assert_relative_eq!(decompressed, dvector![1.0, 0.0, 0.0, 0.0], epsilon = 1e-12);
 
         let residual = compressed - sensing_matrix * decompressed;
         assert_relative_eq!(residual.norm(), expected_tolerance);
         // TODO statistics for algorithms?
     }
+
+    #[test]
+    fn residual_is_orthogonal_to_selected_columns() {
+        let sensing_matrix = dmatrix![
+            1.0, 0.0, 0.6, 0.0;
+            0.0, 1.0, 0.8, 0.6;
+            0.0, 0.0, 0.0, 0.8;
+        ];
+        let compressed = dvector![1.0, 2.0, 3.0];
+        let algorithm = OrthogonalMatchingPursuitSolver {
+            max_iter: 2,
+            tolerance: 0.0,
+        };
+
+        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix);
+
+        let residual = &compressed - &sensing_matrix * &decompressed;
+        let support: Vec<usize> = (0..4).filter(|idx| decompressed[*idx] != 0.0).collect();
+        assert_eq!(support.len(), 2);
+        for idx in support {
+            assert_relative_eq!(
+                sensing_matrix.column(idx).dot(&residual),
+                0.0,
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn skips_dependent_columns() {
+        // column 0 is numerically in the span of column 1, but best matches the residual
+        // after selecting column 1
+        let delta: f64 = 1e-9;
+        let norm = (1.0 + delta * delta).sqrt();
+        let sensing_matrix = dmatrix![
+            1.0, 1.0 / norm,   0.0;
+            0.0, 0.0,          1.0;
+            0.0, delta / norm, 0.0;
+        ];
+        let compressed = dvector![1.0, 1e-12, 1.0];
+        let algorithm = OrthogonalMatchingPursuitSolver {
+            max_iter: 3,
+            tolerance: 0.0,
+        };
+
+        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix);
+
+        let support: Vec<usize> = (0..3).filter(|idx| decompressed[*idx] != 0.0).collect();
+        assert_eq!(support, vec![1, 2]);
+    }
+
+    #[test]
+    fn reused_workspace_gives_same_solution() {
+        let sensing_matrix = dmatrix![
+            1.0, 0.0, 0.6, 0.0;
+            0.0, 1.0, 0.8, 0.6;
+            0.0, 0.0, 0.0, 0.8;
+        ];
+        let algorithm = OrthogonalMatchingPursuitSolver {
+            max_iter: 2,
+            tolerance: 0.0,
+        };
+        let mut workspace = Workspace::new();
+
+        // a smaller problem first, so the buffers have to be resized
+        let small = sensing_matrix.rows(0, 2).into_owned();
+        algorithm.solve_with_workspace(&dvector![1.0, 2.0].column(0), &small, &mut workspace);
+        for compressed in [dvector![1.0, 2.0, 3.0], dvector![-1.0, 0.5, 0.0]] {
+            assert_eq!(
+                algorithm.solve_with_workspace(
+                    &compressed.column(0),
+                    &sensing_matrix,
+                    &mut workspace
+                ),
+                algorithm.solve(&compressed.column(0), &sensing_matrix)
+            );
+        }
+    }
+
+    #[test]
+    fn warm_start_support_is_selected_first() {
+        let sensing_matrix = dmatrix![
+            1.0, 0.0, 0.6, 1.0;
+            0.0, 1.0, 0.8, 0.0;
+            0.0, 0.0, 0.0, 0.0;
+        ];
+        let compressed = dvector![0.6, 0.8, 0.0];
+        let algorithm = OrthogonalMatchingPursuitSolver {
+            max_iter: 2,
+            tolerance: 1e-9,
+        };
+
+        // column 3 duplicates column 0 and is skipped, column 2 would be picked greedily
+        let warm_start = WarmStart::Support(vec![0, 3, 1]);
+        let decompressed = algorithm.solve_warm(
+            &compressed.column(0),
+            &sensing_matrix,
+            &mut Workspace::new(),
+            &warm_start,
+        );
+
+        assert_relative_eq!(decompressed, dvector![0.6, 0.8, 0.0, 0.0], epsilon = 1e-12);
+        assert_relative_eq!(
+            algorithm.solve(&compressed.column(0), &sensing_matrix),
+            dvector![0.0, 0.0, 1.0, 0.0],
+            epsilon = 1e-12
+        );
+    }
 }
@@ -0,0 +1,313 @@
+use nalgebra::{ComplexField, DMatrix, DVector};
+use simba::scalar::SubsetOf;
+
+use super::{
+    group_lasso::spectral_norm_squared, thresholding::soft_threshold, EpsilonSchedule, WarmStart,
+};
+use crate::precision::Precision;
+
+// outer iterations stop once the relative change of the solution is below this
+const CONVERGENCE: f64 = 1e-9;
+
+/// Solver for the weighted l1 problems of [`ReweightedL1Solver`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum L1Solver {
+    /// weighted LASSO, min ½‖y − Ax‖² + λ Σ wᵢ|xᵢ|, by accelerated proximal gradient descent
+    /// from zero, or continuing from the previous solution with a warm start
+    Fista { lambda: f64, max_iter: usize },
+    /// weighted basis pursuit, min Σ wᵢ|xᵢ| subject to Ax = y, by ADMM with penalty `rho`
+    Admm { rho: f64, max_iter: usize },
+}
+
+/// Iteratively reweighted l1 minimization (Candès–Wakin–Boyd).
+///
+/// Repeatedly solves a weighted l1 problem with weights wᵢ = 1/(|xᵢ| + ε) of the previous
+/// solution, which approaches the l0 penalty and recovers sparser signals than plain l1.
+#[derive(Clone, Copy, Debug)]
+pub struct ReweightedL1Solver {
+    outer_iter: usize,
+    epsilon: EpsilonSchedule,
+    inner: L1Solver,
+}
+
+impl ReweightedL1Solver {
+    pub fn with_parameters(
+        outer_iter: usize,
+        epsilon: EpsilonSchedule,
+        inner: L1Solver,
+    ) -> ReweightedL1Solver {
+        ReweightedL1Solver {
+            outer_iter,
+            epsilon,
+            inner,
+        }
+    }
+
+    pub fn solve<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &DMatrix<P>,
+    ) -> DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        self.iterate(y, sensing_matrix, None)
+    }
+
+    /// Starts from the warm start instead of zero, only FISTA makes use of it.
+    pub fn solve_warm<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &DMatrix<P>,
+        warm_start: &WarmStart<P>,
+    ) -> DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        self.iterate(y, sensing_matrix, Some(warm_start))
+    }
+
+    fn iterate<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &DMatrix<P>,
+        warm_start: Option<&WarmStart<P>>,
+    ) -> DVector<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let y: DVector<P> = y.map(|e| nalgebra::convert(e));
+        let mut weights = DVector::<f64>::repeat(sensing_matrix.ncols(), 1.0);
+        let mut epsilon = self.epsilon.initial();
+
+        let mut sparse = match warm_start {
+            Some(warm_start) => warm_start.initial(&y, sensing_matrix),
+            None => DVector::<P>::zeros(sensing_matrix.ncols()),
+        };
+        let zeros = DVector::<P>::zeros(sensing_matrix.ncols());
+        let mut lipschitz = None;
+        for _ in 0..self.outer_iter {
+            let next = match self.inner {
+                L1Solver::Fista { lambda, max_iter } => {
+                    let lipschitz =
+                        *lipschitz.get_or_insert_with(|| spectral_norm_squared(sensing_matrix));
+                    // from zero like a single weighted problem, unless warm started
+                    let initial = if warm_start.is_some() {
+                        &sparse
+                    } else {
+                        &zeros
+                    };
+                    weighted_lasso(
+                        &y,
+                        sensing_matrix,
+                        &weights,
+                        lambda,
+                        max_iter,
+                        lipschitz,
+                        initial,
+                    )
+                }
+                L1Solver::Admm { rho, max_iter } => {
+                    weighted_basis_pursuit(&y, sensing_matrix, &weights, rho, max_iter)
+                }
+            };
+
+            let change: f64 = nalgebra::convert((&next - &sparse).norm());
+            let norm: f64 = nalgebra::convert(next.norm());
+            sparse = next;
+            if norm == 0.0 {
+                break;
+            }
+            let relative_change = change / norm;
+            if relative_change < CONVERGENCE {
+                break;
+            }
+
+            epsilon = self.epsilon.next(epsilon, relative_change);
+            weights = sparse.map(|e| 1.0 / (nalgebra::convert::<_, f64>(e.modulus()) + epsilon));
+        }
+        sparse
+    }
+}
+
+pub(crate) fn weighted_lasso<P>(
+    y: &DVector<P>,
+    sensing_matrix: &DMatrix<P>,
+    weights: &DVector<f64>,
+    lambda: f64,
+    max_iter: usize,
+    lipschitz: f64,
+    initial: &DVector<P>,
+) -> DVector<P>
+where
+    P: Precision,
+    <P as ComplexField>::RealField: SubsetOf<f64>,
+{
+    if lipschitz == 0.0 {
+        return DVector::<P>::zeros(sensing_matrix.ncols());
+    }
+    let mut sparse = initial.clone();
+    let step = 1.0 / lipschitz;
+
+    let mut momentum = sparse.clone();
+    let mut t = 1.0_f64;
+    for _ in 0..max_iter {
+        let gradient = sensing_matrix.ad_mul(&(sensing_matrix * &momentum - y));
+        let mut next = &momentum - gradient * nalgebra::convert::<f64, P>(step);
+        next.zip_apply(weights, |e, w| *e = soft_threshold(*e, lambda * step * w));
+
+        let t_next = (1.0 + (1.0 + 4.0 * t * t).sqrt()) / 2.0;
+        momentum = &next + (&next - &sparse) * nalgebra::convert::<f64, P>((t - 1.0) / t_next);
+        t = t_next;
+        sparse = next;
+    }
+    sparse
+}
+
+fn weighted_basis_pursuit<P>(
+    y: &DVector<P>,
+    sensing_matrix: &DMatrix<P>,
+    weights: &DVector<f64>,
+    rho: f64,
+    max_iter: usize,
+) -> DVector<P>
+where
+    P: Precision,
+    <P as ComplexField>::RealField: SubsetOf<f64>,
+{
+    let len = sensing_matrix.ncols();
+    // projection onto {x | Ax = y}, v − Aᴴ(AAᴴ)⁻¹(Av − y)
+    let Some(gram) = (sensing_matrix * sensing_matrix.adjoint()).cholesky() else {
+        return DVector::zeros(len);
+    };
+    let project = |v: DVector<P>| {
+        let correction = sensing_matrix.ad_mul(&gram.solve(&(sensing_matrix * &v - y)));
+        v - correction
+    };
+
+    let mut z = DVector::<P>::zeros(len);
+    let mut u = DVector::<P>::zeros(len);
+    for _ in 0..max_iter {
+        let x = project(&z - &u);
+        let mut next = &x + &u;
+        next.zip_apply(weights, |e, w| *e = soft_threshold(*e, w / rho));
+        u += &x - &next;
+
+        let change: f64 = nalgebra::convert((&next - &z).norm());
+        let primal: f64 = nalgebra::convert((&x - &next).norm());
+        z = next;
+        if change < CONVERGENCE && primal < CONVERGENCE {
+            break;
+        }
+    }
+    z
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use nalgebra::{DMatrix, DVector};
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    use super::{weighted_lasso, L1Solver, ReweightedL1Solver};
+    use crate::algorithm::{group_lasso::spectral_norm_squared, EpsilonSchedule};
+
+    fn problem() -> (DMatrix<f64>, DVector<f64>) {
+        let mut rng = StdRng::seed_from_u64(42);
+        let sensing_matrix = DMatrix::<f64>::from_fn(24, 64, |_, _| {
+            let v: f64 = StandardNormal.sample(&mut rng);
+            v / 24.0_f64.sqrt()
+        });
+        let mut expected = DVector::<f64>::zeros(64);
+        for (idx, value) in [
+            (3, 1.0),
+            (17, -2.0),
+            (25, 0.5),
+            (40, 1.5),
+            (52, -0.7),
+            (60, 0.3),
+        ] {
+            expected[idx] = value;
+        }
+        (sensing_matrix, expected)
+    }
+
+    #[test]
+    fn admm() {
+        let (sensing_matrix, expected) = problem();
+        let compressed = &sensing_matrix * &expected;
+
+        let algorithm = ReweightedL1Solver::with_parameters(
+            8,
+            EpsilonSchedule::Fixed(0.1),
+            L1Solver::Admm {
+                rho: 1.0,
+                max_iter: 2000,
+            },
+        );
+        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix);
+
+        assert_relative_eq!(expected, decompressed, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn fista() {
+        let (sensing_matrix, expected) = problem();
+        let compressed = &sensing_matrix * &expected;
+
+        let algorithm = ReweightedL1Solver::with_parameters(
+            8,
+            EpsilonSchedule::Fixed(0.1),
+            L1Solver::Fista {
+                lambda: 1e-4,
+                max_iter: 2000,
+            },
+        );
+        let decompressed = algorithm.solve(&compressed.column(0), &sensing_matrix);
+
+        assert_relative_eq!(expected, decompressed, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn fista_starts_from_zero_without_warm_start() {
+        let (sensing_matrix, expected) = problem();
+        let compressed = &sensing_matrix * &expected;
+        let (lambda, max_iter) = (1e-2, 50);
+        let lipschitz = spectral_norm_squared(&sensing_matrix);
+        let zeros = DVector::<f64>::zeros(64);
+
+        let first = weighted_lasso(
+            &compressed,
+            &sensing_matrix,
+            &DVector::repeat(64, 1.0),
+            lambda,
+            max_iter,
+            lipschitz,
+            &zeros,
+        );
+        let weights = first.map(|e| 1.0 / (e.abs() + 0.1));
+        let second = weighted_lasso(
+            &compressed,
+            &sensing_matrix,
+            &weights,
+            lambda,
+            max_iter,
+            lipschitz,
+            &zeros,
+        );
+
+        let algorithm = ReweightedL1Solver::with_parameters(
+            2,
+            EpsilonSchedule::Fixed(0.1),
+            L1Solver::Fista { lambda, max_iter },
+        );
+        assert_eq!(
+            algorithm.solve(&compressed.column(0), &sensing_matrix),
+            second
+        );
+    }
+}
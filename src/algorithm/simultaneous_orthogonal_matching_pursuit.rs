@@ -0,0 +1,127 @@
+use crate::precision::Precision;
+use nalgebra::ComplexField;
+use simba::scalar::SubsetOf;
+
+/// Simultaneous OMP (SOMP) for multiple measurement vectors sharing the same sparse support.
+///
+/// Each iteration selects the column with the largest correlation energy over all channels.
+#[derive(Clone, Copy, Debug)]
+pub struct SimultaneousOrthogonalMatchingPursuitSolver {
+    max_iter: usize,
+    tolerance: f64,
+}
+
+impl SimultaneousOrthogonalMatchingPursuitSolver {
+    pub fn with_parameters(
+        max_iter: usize,
+        tolerance: f64,
+    ) -> SimultaneousOrthogonalMatchingPursuitSolver {
+        SimultaneousOrthogonalMatchingPursuitSolver {
+            max_iter,
+            tolerance,
+        }
+    }
+
+    /// Solves for a row sparse coefficient matrix, `y` holds one channel per column.
+    pub fn solve<P>(
+        &self,
+        y: &nalgebra::DMatrix<f64>,
+        sensing_matrix: &nalgebra::DMatrix<P>,
+    ) -> nalgebra::DMatrix<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let compressed: nalgebra::DMatrix<P> = y.map(|e| nalgebra::convert(e));
+        let mut sparse = nalgebra::DMatrix::<P>::zeros(sensing_matrix.ncols(), y.ncols());
+        let mut residual = compressed.clone();
+        let mut selected_column_idxs = Vec::<usize>::new();
+
+        let max_iter = self.max_iter.min(sensing_matrix.ncols());
+        for _ in 0..max_iter {
+            let inner_products = sensing_matrix.ad_mul(&residual);
+            let max_idx = inner_products
+                .row_iter()
+                .enumerate()
+                .filter(|(idx, _)| !selected_column_idxs.contains(idx))
+                .map(|(idx, row)| (idx, nalgebra::convert::<_, f64>(row.norm())))
+                .max_by(|(_, a), (_, b)| a.partial_cmp(b).expect("Can't compare, probably nan"))
+                .map(|(idx, _)| idx)
+                .unwrap();
+
+            selected_column_idxs.push(max_idx);
+            let selected_basis = sensing_matrix.select_columns(&selected_column_idxs);
+
+            // least squares for all channels at once
+            let svd = nalgebra::linalg::SVD::new(selected_basis.clone(), true, true);
+            let coefficients = svd
+                .solve(&compressed, nalgebra::convert(f64::EPSILON))
+                .unwrap();
+            for (row, idx) in selected_column_idxs.iter().enumerate() {
+                sparse.set_row(*idx, &coefficients.row(row));
+            }
+
+            residual = &compressed - selected_basis * coefficients;
+            if nalgebra::convert::<_, f64>(residual.norm()) < self.tolerance {
+                break;
+            }
+        }
+
+        sparse
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use nalgebra::{dmatrix, DMatrix};
+
+    use super::SimultaneousOrthogonalMatchingPursuitSolver;
+
+    const ONE_HALF: f64 = 1.0 / 2.0;
+    const ONE_THIRD: f64 = 1.0 / 3.0;
+
+    #[test]
+    fn selects_common_support() {
+        let sensing_matrix = dmatrix![
+            1.0, 0.0            , 0.0, ONE_THIRD.sqrt();
+            0.0, ONE_HALF.sqrt(), 0.0, ONE_THIRD.sqrt();
+            0.0, ONE_HALF.sqrt(), 1.0, ONE_THIRD.sqrt();
+        ];
+        // both channels use the columns 1 and 2
+        let expected = dmatrix![
+            0.0, 0.0;
+            1.0, -2.0;
+            0.5, 1.0;
+            0.0, 0.0;
+        ];
+        let compressed = &sensing_matrix * &expected;
+
+        let algorithm = SimultaneousOrthogonalMatchingPursuitSolver::with_parameters(2, 1e-9);
+        let decompressed = algorithm.solve(&compressed, &sensing_matrix);
+
+        assert_relative_eq!(expected, decompressed, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn should_abort_when_residual_energy_is_below_tolerance() {
+        let sensing_matrix = DMatrix::<f64>::identity(3, 3);
+        let compressed = dmatrix![
+            2.0, 2.0;
+            0.1, 0.0;
+            0.0, 0.1;
+        ];
+
+        let algorithm = SimultaneousOrthogonalMatchingPursuitSolver::with_parameters(3, 0.5);
+        let decompressed = algorithm.solve(&compressed, &sensing_matrix);
+
+        assert_eq!(
+            decompressed,
+            dmatrix![
+                2.0, 2.0;
+                0.0, 0.0;
+                0.0, 0.0;
+            ]
+        );
+    }
+}
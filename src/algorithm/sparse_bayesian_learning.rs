@@ -0,0 +1,232 @@
+use nalgebra::{ComplexField, DMatrix, DVector};
+use simba::scalar::SubsetOf;
+
+use crate::precision::Precision;
+
+/// Sparse Bayesian learning (relevance vector machine).
+///
+/// Each coefficient gets a zero mean gaussian prior with its own variance γᵢ, the variances
+/// and the noise variance are learned by maximizing the marginal likelihood, with Tipping's
+/// fixed point updates for γ and the EM update of Wipf–Rao for the noise. Irrelevant
+/// coefficients are driven to γᵢ = 0, the result is the gaussian posterior of the coefficients.
+#[derive(Clone, Copy, Debug)]
+pub struct SparseBayesianLearningSolver {
+    max_iter: usize,
+    tolerance: f64,
+    noise_variance: Option<f64>,
+}
+
+/// Gaussian posterior of the coefficients.
+#[derive(Clone, Debug)]
+pub struct SparseBayesianEstimate<P>
+where
+    P: Precision,
+{
+    pub mean: DVector<P>,
+    pub covariance: DMatrix<P>,
+    /// known or estimated variance of the measurement noise
+    pub noise_variance: f64,
+}
+
+// prior variances below are pruned
+const MIN_GAMMA: f64 = 1e-12;
+
+// relative to the mean energy of the measurements, keeps the noiseless case well conditioned
+const MIN_NOISE_VARIANCE: f64 = 1e-10;
+
+impl SparseBayesianLearningSolver {
+    /// Iterates until the largest change of the prior variances relative to their maximum is
+    /// below `tolerance`.
+    pub fn with_parameters(max_iter: usize, tolerance: f64) -> SparseBayesianLearningSolver {
+        SparseBayesianLearningSolver {
+            max_iter,
+            tolerance,
+            noise_variance: None,
+        }
+    }
+
+    /// Known noise variance, otherwise it is estimated along with the prior variances.
+    pub fn with_noise_variance(self, noise_variance: f64) -> SparseBayesianLearningSolver {
+        SparseBayesianLearningSolver {
+            noise_variance: Some(noise_variance),
+            ..self
+        }
+    }
+
+    pub fn solve<P>(
+        &self,
+        y: &nalgebra::DVectorView<f64>,
+        sensing_matrix: &DMatrix<P>,
+    ) -> SparseBayesianEstimate<P>
+    where
+        P: Precision,
+        <P as ComplexField>::RealField: SubsetOf<f64>,
+    {
+        let y: DVector<P> = y.map(|e| nalgebra::convert(e));
+        let (nmeasurements, len) = sensing_matrix.shape();
+        let energy = nalgebra::convert::<_, f64>(y.norm_squared()) / nmeasurements as f64;
+        let min_noise_variance = MIN_NOISE_VARIANCE * energy;
+
+        let mut gamma = DVector::<f64>::repeat(len, 1.0);
+        let mut noise_variance = self.noise_variance.unwrap_or(0.1 * energy);
+        let mut estimate = posterior(&y, sensing_matrix, &gamma, noise_variance);
+        for _ in 0..self.max_iter {
+            let Some(SparseBayesianEstimate {
+                mean, covariance, ..
+            }) = &estimate
+            else {
+                break;
+            };
+            let variances = covariance
+                .diagonal()
+                .map(|e| nalgebra::convert::<_, f64>(e.real()));
+            // how well each coefficient is determined by the data, 1 − Σᵢᵢ/γᵢ
+            let determined = DVector::from_fn(len, |i, _| {
+                if gamma[i] > 0.0 {
+                    (1.0 - variances[i] / gamma[i]).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                }
+            });
+            let next_gamma = DVector::from_fn(len, |i, _| {
+                let magnitude: f64 = nalgebra::convert(mean[i].modulus_squared());
+                if determined[i] > 0.0 && magnitude / determined[i] > MIN_GAMMA {
+                    magnitude / determined[i]
+                } else {
+                    0.0
+                }
+            });
+
+            if self.noise_variance.is_none() {
+                let residual: f64 = nalgebra::convert((&y - sensing_matrix * mean).norm_squared());
+                noise_variance = ((residual + noise_variance * determined.sum())
+                    / nmeasurements as f64)
+                    .max(min_noise_variance);
+            }
+
+            let max_gamma = next_gamma.max();
+            let change = (&next_gamma - &gamma).abs().max();
+            gamma = next_gamma;
+            estimate = posterior(&y, sensing_matrix, &gamma, noise_variance);
+            if max_gamma == 0.0 || change < self.tolerance * max_gamma {
+                break;
+            }
+        }
+
+        estimate.unwrap_or_else(|| SparseBayesianEstimate {
+            mean: DVector::zeros(len),
+            covariance: DMatrix::zeros(len, len),
+            noise_variance,
+        })
+    }
+}
+
+impl<P> SparseBayesianEstimate<P>
+where
+    P: Precision,
+    <P as ComplexField>::RealField: SubsetOf<f64>,
+{
+    /// Posterior variances of the coefficients, i.e. the diagonal of the covariance.
+    pub fn variances(&self) -> DVector<f64> {
+        self.covariance
+            .diagonal()
+            .map(|e| nalgebra::convert::<_, f64>(e.real()).max(0.0))
+    }
+}
+
+// posterior for the prior variances gamma, computed with the m×m measurement covariance
+// Σy = σ²I + AΓAᴴ: mean ΓAᴴΣy⁻¹y and covariance Γ − ΓAᴴΣy⁻¹AΓ
+fn posterior<P>(
+    y: &DVector<P>,
+    sensing_matrix: &DMatrix<P>,
+    gamma: &DVector<f64>,
+    noise_variance: f64,
+) -> Option<SparseBayesianEstimate<P>>
+where
+    P: Precision,
+    <P as ComplexField>::RealField: SubsetOf<f64>,
+{
+    let nmeasurements = sensing_matrix.nrows();
+    let mut weighted = sensing_matrix.clone();
+    for (mut column, g) in weighted.column_iter_mut().zip(gamma.iter()) {
+        column *= nalgebra::convert::<f64, P>(*g);
+    }
+    let measurement_covariance = &weighted * sensing_matrix.adjoint()
+        + DMatrix::<P>::identity(nmeasurements, nmeasurements)
+            * nalgebra::convert::<f64, P>(noise_variance);
+    let cholesky = measurement_covariance.cholesky()?;
+
+    let gain = cholesky.solve(&weighted);
+    let mean = gain.ad_mul(y);
+    let covariance = DMatrix::<P>::from_diagonal(&gamma.map(|g| nalgebra::convert::<f64, P>(g)))
+        - weighted.ad_mul(&gain);
+    Some(SparseBayesianEstimate {
+        mean,
+        covariance,
+        noise_variance,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use nalgebra::{DMatrix, DVector};
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    use super::SparseBayesianLearningSolver;
+
+    fn problem(rng: &mut StdRng, nmeasurements: usize) -> (DMatrix<f64>, DVector<f64>) {
+        let sensing_matrix = DMatrix::<f64>::from_fn(nmeasurements, 64, |_, _| {
+            let v: f64 = StandardNormal.sample(rng);
+            v / (nmeasurements as f64).sqrt()
+        });
+        let mut expected = DVector::<f64>::zeros(64);
+        for (idx, value) in [(3, 1.0), (17, -2.0), (25, 0.5), (40, 1.5), (52, -0.7)] {
+            expected[idx] = value;
+        }
+        (sensing_matrix, expected)
+    }
+
+    #[test]
+    fn recovers_noiseless() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let (sensing_matrix, expected) = problem(&mut rng, 32);
+        let compressed = &sensing_matrix * &expected;
+
+        let algorithm = SparseBayesianLearningSolver::with_parameters(300, 1e-6);
+        let estimate = algorithm.solve(&compressed.column(0), &sensing_matrix);
+
+        assert_relative_eq!(expected, estimate.mean, epsilon = 1e-3);
+        assert!(estimate.variances().max() < 1e-3);
+    }
+
+    #[test]
+    fn estimates_noise_and_uncertainty() {
+        const M: usize = 64;
+        let mut rng = StdRng::seed_from_u64(42);
+        let (sensing_matrix, expected) = problem(&mut rng, M);
+        let sigma = 0.02;
+        let noise = DVector::<f64>::from_fn(M, |_, _| {
+            let v: f64 = StandardNormal.sample(&mut rng);
+            sigma * v
+        });
+        let compressed = &sensing_matrix * &expected + noise;
+
+        let algorithm = SparseBayesianLearningSolver::with_parameters(300, 1e-6);
+        let estimate = algorithm.solve(&compressed.column(0), &sensing_matrix);
+
+        assert!(estimate.noise_variance > sigma * sigma / 4.0);
+        assert!(estimate.noise_variance < sigma * sigma * 4.0);
+        // the error of the large coefficients is within three standard deviations
+        let variances = estimate.variances();
+        for idx in [3, 17, 40] {
+            assert!((estimate.mean[idx] - expected[idx]).abs() < 3.0 * variances[idx].sqrt());
+        }
+
+        let known = algorithm.with_noise_variance(sigma * sigma);
+        let estimate = known.solve(&compressed.column(0), &sensing_matrix);
+        assert_eq!(estimate.noise_variance, sigma * sigma);
+        assert!((&estimate.mean - &expected).norm() < 0.2);
+    }
+}
@@ -0,0 +1,78 @@
+use nalgebra::{ComplexField, DVector};
+use num_traits::Zero;
+use simba::scalar::SubsetOf;
+
+use crate::precision::Precision;
+
+/// Keeps the `sparsity` entries with the largest modulus and sets all others to zero.
+pub(crate) fn hard_threshold<P>(vector: &mut DVector<P>, sparsity: usize)
+where
+    P: Precision,
+{
+    if sparsity >= vector.len() {
+        return;
+    }
+    let mut order: Vec<usize> = (0..vector.len()).collect();
+    order.sort_by(|&a, &b| {
+        vector[b]
+            .modulus()
+            .partial_cmp(&vector[a].modulus())
+            .expect("Can't compare, probably nan")
+    });
+    for &idx in &order[sparsity..] {
+        vector[idx] = P::zero();
+    }
+}
+
+/// Shrinks the modulus by `threshold`, values below become zero.
+pub(crate) fn soft_threshold<P>(value: P, threshold: f64) -> P
+where
+    P: Precision,
+    <P as ComplexField>::RealField: SubsetOf<f64>,
+{
+    let modulus: f64 = nalgebra::convert(value.modulus());
+    if modulus > threshold {
+        value * nalgebra::convert::<f64, P>(1.0 - threshold / modulus)
+    } else {
+        P::zero()
+    }
+}
+
+/// Sign of the real part, mapping zero to +1.
+pub(crate) fn sign<P>(value: P) -> P
+where
+    P: Precision,
+{
+    if value.real() >= <P as ComplexField>::RealField::zero() {
+        P::one()
+    } else {
+        -P::one()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nalgebra::dvector;
+
+    use super::{hard_threshold, sign, soft_threshold};
+
+    #[test]
+    fn keeps_largest_entries() {
+        let mut v = dvector![0.5, -3.0, 1.0, 2.0];
+        hard_threshold(&mut v, 2);
+        assert_eq!(v, dvector![0.0, -3.0, 0.0, 2.0]);
+    }
+
+    #[test]
+    fn shrinks_towards_zero() {
+        assert_eq!(soft_threshold(3.0, 1.0), 2.0);
+        assert_eq!(soft_threshold(-3.0, 1.0), -2.0);
+        assert_eq!(soft_threshold(0.5, 1.0), 0.0);
+    }
+
+    #[test]
+    fn sign_of_zero_is_positive() {
+        assert_eq!(sign(0.0), 1.0);
+        assert_eq!(sign(-0.1), -1.0);
+    }
+}
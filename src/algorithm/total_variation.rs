@@ -0,0 +1,217 @@
+use nalgebra::{DMatrix, DVector, DVectorView};
+
+/// Layout of the signal, defining its discrete gradient.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignalShape {
+    Signal1d,
+    /// image flattened row by row
+    Image {
+        width: usize,
+    },
+}
+
+// the primal dual iteration stops once the relative change of the solution is below this
+const CONVERGENCE: f64 = 1e-10;
+
+/// Total variation minimization, min TV(x) subject to ‖y − Φx‖ ≤ tolerance.
+///
+/// Recovers signals which are sparse in their gradient, e.g. piecewise constant signals and
+/// images, directly from the measurement matrix Φ without a sparsifying transformation.
+/// Solved with the Chambolle–Pock primal-dual algorithm, the TV of images is isotropic.
+#[derive(Clone, Copy, Debug)]
+pub struct TotalVariationSolver {
+    shape: SignalShape,
+    max_iter: usize,
+    tolerance: f64,
+}
+
+impl TotalVariationSolver {
+    pub fn with_parameters(
+        shape: SignalShape,
+        max_iter: usize,
+        tolerance: f64,
+    ) -> TotalVariationSolver {
+        TotalVariationSolver {
+            shape,
+            max_iter,
+            tolerance,
+        }
+    }
+
+    pub fn with_tolerance(self, tolerance: f64) -> TotalVariationSolver {
+        TotalVariationSolver { tolerance, ..self }
+    }
+
+    /// Panics if the shape doesn't fit signals of length `len`, images consist of full rows.
+    pub fn assert_shape(&self, len: usize) {
+        if let SignalShape::Image { width } = self.shape {
+            assert!(
+                width > 0 && len.is_multiple_of(width),
+                "signal of length {len} can't be split into image rows of width {width}"
+            );
+        }
+    }
+
+    pub fn solve(&self, y: &DVectorView<f64>, measurement_matrix: &DMatrix<f64>) -> DVector<f64> {
+        let len = measurement_matrix.ncols();
+        self.assert_shape(len);
+        let y = y.clone_owned();
+
+        let measurement_norm = nalgebra::linalg::SVD::new(measurement_matrix.clone(), false, false)
+            .singular_values
+            .max();
+        // squared operator norm of the gradient is bounded by 4 per dimension
+        let gradient_norm = match self.shape {
+            SignalShape::Signal1d => 4.0,
+            SignalShape::Image { .. } => 8.0,
+        };
+        let step = 0.99 / (gradient_norm + measurement_norm * measurement_norm).sqrt();
+
+        let mut x = DVector::<f64>::zeros(len);
+        let mut x_bar = x.clone();
+        let mut p = self.gradient(&x);
+        let mut q = DVector::<f64>::zeros(y.len());
+        for _ in 0..self.max_iter {
+            // dual ascent, the TV part projects onto the unit ball of the dual norm
+            p += self.gradient(&x_bar) * step;
+            self.project_dual(&mut p);
+
+            // moreau identity for the indicator of the ball around y
+            q += measurement_matrix * &x_bar * step;
+            let center = &q / step - &y;
+            let center_norm = center.norm();
+            let projected = if center_norm > self.tolerance {
+                &y + center * (self.tolerance / center_norm)
+            } else {
+                &q / step
+            };
+            q -= projected * step;
+
+            // primal descent
+            let next = &x - (self.divergence_adjoint(&p) + measurement_matrix.tr_mul(&q)) * step;
+            x_bar = &next * 2.0 - &x;
+            let change = (&next - &x).norm();
+            let norm = next.norm();
+            x = next;
+            if norm > 0.0 && change / norm < CONVERGENCE {
+                break;
+            }
+        }
+        x
+    }
+
+    // forward differences, zero at the boundary
+    fn gradient(&self, x: &DVector<f64>) -> DVector<f64> {
+        let len = x.len();
+        match self.shape {
+            SignalShape::Signal1d => {
+                DVector::from_fn(len, |i, _| if i + 1 < len { x[i + 1] - x[i] } else { 0.0 })
+            }
+            SignalShape::Image { width } => {
+                let height = len / width;
+                // horizontal differences followed by vertical ones
+                DVector::from_fn(2 * len, |k, _| {
+                    let i = k % len;
+                    let (row, col) = (i / width, i % width);
+                    if k < len {
+                        if col + 1 < width {
+                            x[i + 1] - x[i]
+                        } else {
+                            0.0
+                        }
+                    } else if row + 1 < height {
+                        x[i + width] - x[i]
+                    } else {
+                        0.0
+                    }
+                })
+            }
+        }
+    }
+
+    // adjoint of the gradient, i.e. the negative divergence
+    fn divergence_adjoint(&self, p: &DVector<f64>) -> DVector<f64> {
+        match self.shape {
+            SignalShape::Signal1d => {
+                let len = p.len();
+                DVector::from_fn(len, |i, _| {
+                    let previous = if i > 0 { p[i - 1] } else { 0.0 };
+                    let current = if i + 1 < len { p[i] } else { 0.0 };
+                    previous - current
+                })
+            }
+            SignalShape::Image { width } => {
+                let len = p.len() / 2;
+                let height = len / width;
+                DVector::from_fn(len, |i, _| {
+                    let (row, col) = (i / width, i % width);
+                    let left = if col > 0 { p[i - 1] } else { 0.0 };
+                    let right = if col + 1 < width { p[i] } else { 0.0 };
+                    let up = if row > 0 { p[len + i - width] } else { 0.0 };
+                    let down = if row + 1 < height { p[len + i] } else { 0.0 };
+                    left - right + up - down
+                })
+            }
+        }
+    }
+
+    fn project_dual(&self, p: &mut DVector<f64>) {
+        match self.shape {
+            SignalShape::Signal1d => p.apply(|e| *e = e.clamp(-1.0, 1.0)),
+            SignalShape::Image { .. } => {
+                let len = p.len() / 2;
+                for i in 0..len {
+                    let norm = p[i].hypot(p[len + i]);
+                    if norm > 1.0 {
+                        p[i] /= norm;
+                        p[len + i] /= norm;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use nalgebra::{DMatrix, DVector};
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    use super::{SignalShape, TotalVariationSolver};
+
+    #[test]
+    fn gradient_adjoint() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for shape in [SignalShape::Signal1d, SignalShape::Image { width: 4 }] {
+            let solver = TotalVariationSolver::with_parameters(shape, 1, 0.0);
+            let x = DVector::<f64>::from_fn(12, |_, _| StandardNormal.sample(&mut rng));
+            let gradient = solver.gradient(&x);
+            let p = DVector::<f64>::from_fn(gradient.len(), |_, _| StandardNormal.sample(&mut rng));
+
+            assert_relative_eq!(
+                gradient.dot(&p),
+                x.dot(&solver.divergence_adjoint(&p)),
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn recovers_piecewise_constant_signal() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let measurement_matrix = DMatrix::<f64>::from_fn(20, 64, |_, _| {
+            let v: f64 = StandardNormal.sample(&mut rng);
+            v / 8.0
+        });
+        let expected =
+            DVector::<f64>::from_fn(64, |i, _| if (20..41).contains(&i) { 1.0 } else { -0.5 });
+        let compressed = &measurement_matrix * &expected;
+
+        let solver = TotalVariationSolver::with_parameters(SignalShape::Signal1d, 20000, 0.0);
+        let decompressed = solver.solve(&compressed.column(0), &measurement_matrix);
+
+        assert_relative_eq!(expected, decompressed, epsilon = 1e-4);
+    }
+}
@@ -0,0 +1,125 @@
+use nalgebra::{ComplexField, DVector};
+use simba::scalar::SubsetOf;
+
+use crate::precision::Precision;
+
+/// Parent-child structure of wavelet coefficients, ordered from coarse to fine as produced by
+/// the Haar transformations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WaveletTree {
+    /// binary tree of a 1D decomposition, the parent of coefficient i is i/2
+    Binary,
+    /// quad tree of a 2D decomposition of a square image flattened row by row, the parent of
+    /// coefficient (row, col) is (row/2, col/2)
+    Quad,
+}
+
+impl WaveletTree {
+    /// Parent of coefficient `idx` of `len` coefficients, `None` for the root.
+    pub fn parent(&self, idx: usize, len: usize) -> Option<usize> {
+        if idx == 0 {
+            return None;
+        }
+        match self {
+            WaveletTree::Binary => Some(idx / 2),
+            WaveletTree::Quad => {
+                let width = (len as f64).sqrt().round() as usize;
+                let (row, col) = (idx / width, idx % width);
+                Some((row / 2) * width + col / 2)
+            }
+        }
+    }
+}
+
+/// Projects onto the coefficient vectors whose support is a rooted subtree of at most
+/// `sparsity` nodes.
+///
+/// Greedy variant of the condensing sort and select algorithm (CSSA): a node is only selected
+/// together with its not yet selected ancestors, condensed into one supernode scored by its
+/// average energy. The supernode with the largest score fitting into the budget is added until
+/// the budget is used up.
+pub(crate) fn tree_approximate<P>(vector: &mut DVector<P>, tree: WaveletTree, sparsity: usize)
+where
+    P: Precision,
+    <P as ComplexField>::RealField: SubsetOf<f64>,
+{
+    let len = vector.len();
+    let energy: Vec<f64> = vector
+        .iter()
+        .map(|e| nalgebra::convert::<_, f64>(e.modulus_squared()))
+        .collect();
+
+    let mut selected = vec![false; len];
+    let mut budget = sparsity.min(len);
+    while budget > 0 {
+        let mut best: Option<(Vec<usize>, f64)> = None;
+        for idx in (0..len).filter(|idx| !selected[*idx] && energy[*idx] > 0.0) {
+            let path = unselected_path(idx, tree, len, &selected);
+            if path.len() > budget {
+                continue;
+            }
+            let score = path.iter().map(|node| energy[*node]).sum::<f64>() / path.len() as f64;
+            if best
+                .as_ref()
+                .is_none_or(|(_, best_score)| score > *best_score)
+            {
+                best = Some((path, score));
+            }
+        }
+        let Some((path, _)) = best else {
+            break;
+        };
+        budget -= path.len();
+        for node in path {
+            selected[node] = true;
+        }
+    }
+
+    for (entry, selected) in vector.iter_mut().zip(selected) {
+        if !selected {
+            *entry = P::zero();
+        }
+    }
+}
+
+// the node and its ancestors up to the first selected one (or the root)
+fn unselected_path(idx: usize, tree: WaveletTree, len: usize, selected: &[bool]) -> Vec<usize> {
+    let mut path = vec![idx];
+    let mut node = idx;
+    while let Some(parent) = tree.parent(node, len) {
+        if selected[parent] {
+            break;
+        }
+        path.push(parent);
+        node = parent;
+    }
+    path
+}
+
+#[cfg(test)]
+mod test {
+    use nalgebra::dvector;
+
+    use super::{tree_approximate, WaveletTree};
+
+    #[test]
+    fn quad_tree_parents() {
+        // 4x4 image
+        assert_eq!(WaveletTree::Quad.parent(0, 16), None);
+        assert_eq!(WaveletTree::Quad.parent(5, 16), Some(0));
+        assert_eq!(WaveletTree::Quad.parent(15, 16), Some(5));
+        assert_eq!(WaveletTree::Quad.parent(2, 16), Some(1));
+    }
+
+    #[test]
+    fn keeps_rooted_subtree() {
+        // the large leaf 6 pulls in its ancestors 3, 1 and 0
+        let mut v = dvector![0.1, 0.2, 0.9, 0.0, 0.0, 0.0, 5.0, 0.0];
+        tree_approximate(&mut v, WaveletTree::Binary, 4);
+        assert_eq!(v, dvector![0.1, 0.2, 0.0, 0.0, 0.0, 0.0, 5.0, 0.0]);
+
+        let mut v = dvector![0.1, 0.2, 0.9, 0.0, 0.0, 0.0, 5.0, 0.0];
+        tree_approximate(&mut v, WaveletTree::Binary, 2);
+        assert_eq!(v, dvector![0.1, 0.2, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+    }
+}
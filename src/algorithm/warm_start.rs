@@ -0,0 +1,112 @@
+use nalgebra::ComplexField;
+use simba::scalar::SubsetOf;
+
+use crate::precision::Precision;
+
+/// Initial estimate of a solver, e.g. from the previous of temporally correlated frames.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WarmStart<P>
+where
+    P: Precision,
+{
+    /// indices of the coefficients expected to be non zero
+    Support(Vec<usize>),
+    /// coefficients of a previous solution
+    Solution(nalgebra::DVector<P>),
+}
+
+impl<P> WarmStart<P>
+where
+    P: Precision,
+    <P as ComplexField>::RealField: SubsetOf<f64>,
+{
+    pub fn support(&self) -> Vec<usize> {
+        match self {
+            WarmStart::Support(support) => support.clone(),
+            WarmStart::Solution(solution) => support_of(solution.as_slice(), 0.0),
+        }
+    }
+
+    /// Starting point of iterative solvers, a support is turned into the least squares fit of
+    /// `y` on its columns.
+    pub(crate) fn initial(
+        &self,
+        y: &nalgebra::DVector<P>,
+        sensing_matrix: &nalgebra::DMatrix<P>,
+    ) -> nalgebra::DVector<P> {
+        let len = sensing_matrix.ncols();
+        match self {
+            WarmStart::Solution(solution) if solution.len() == len => solution.clone(),
+            _ => {
+                let mut support = self.support();
+                support.retain(|idx| *idx < len);
+                support.sort_unstable();
+                support.dedup();
+
+                let mut initial = nalgebra::DVector::zeros(len);
+                if support.is_empty() {
+                    return initial;
+                }
+                let svd =
+                    nalgebra::linalg::SVD::new(sensing_matrix.select_columns(&support), true, true);
+                let coefficients = svd
+                    .solve(y, nalgebra::convert(f64::EPSILON.sqrt()))
+                    .expect("SVD with singular vectors");
+                for (idx, coefficient) in support.iter().zip(coefficients.iter()) {
+                    initial[*idx] = *coefficient;
+                }
+                initial
+            }
+        }
+    }
+}
+
+/// Indices of the coefficients with a modulus above `relative_threshold` times the largest one,
+/// all non zero coefficients for a threshold of zero.
+pub(crate) fn support_of<P>(coefficients: &[P], relative_threshold: f64) -> Vec<usize>
+where
+    P: Precision,
+    <P as ComplexField>::RealField: SubsetOf<f64>,
+{
+    let modulus = |e: &P| nalgebra::convert::<_, f64>((*e).modulus());
+    let threshold = relative_threshold * coefficients.iter().map(modulus).fold(0.0, f64::max);
+    coefficients
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| !e.is_zero() && modulus(e) > threshold)
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use nalgebra::{dmatrix, dvector};
+
+    use super::{support_of, WarmStart};
+
+    #[test]
+    fn support_is_fitted_by_least_squares() {
+        let sensing_matrix = dmatrix![
+            1.0, 0.0, 0.0;
+            0.0, 1.0, 0.0;
+            0.0, 0.0, 1.0;
+        ];
+        let y = dvector![1.0, 2.0, 3.0];
+
+        let warm_start = WarmStart::Support(vec![2, 0, 7]);
+        assert_relative_eq!(
+            warm_start.initial(&y, &sensing_matrix),
+            dvector![1.0, 0.0, 3.0],
+            epsilon = 1e-12
+        );
+
+        let solution = dvector![0.0, -1.0, 0.5];
+        assert_eq!(support_of(solution.as_slice(), 0.0), vec![1, 2]);
+        assert_eq!(support_of(solution.as_slice(), 0.6), vec![1]);
+        assert_eq!(
+            WarmStart::Solution(solution.clone()).initial(&y, &sensing_matrix),
+            solution
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use std::{
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use crate::precision::Precision;
+
+/// Number of columns up to which models precompute the Gram matrix, 8 MiB for real matrices.
+pub const DEFAULT_MAX_GRAM_COLUMNS: usize = 1024;
+
+/// Adjoint and Gram matrix of a fixed sensing matrix, computed once and shared by workspaces.
+#[derive(Clone, Debug)]
+pub struct Precomputed<P>
+where
+    P: Precision,
+{
+    adjoint: nalgebra::DMatrix<P>,
+    gram: Option<nalgebra::DMatrix<P>>,
+}
+
+impl<P> Precomputed<P>
+where
+    P: Precision,
+{
+    /// The Gram matrix AᴴA is only computed for at most `max_gram_columns` columns, as it
+    /// grows quadratically.
+    pub fn new(sensing_matrix: &nalgebra::DMatrix<P>, max_gram_columns: usize) -> Self {
+        let adjoint = sensing_matrix.adjoint();
+        let gram = (sensing_matrix.ncols() <= max_gram_columns).then(|| &adjoint * sensing_matrix);
+        Self { adjoint, gram }
+    }
+
+    pub fn adjoint(&self) -> &nalgebra::DMatrix<P> {
+        &self.adjoint
+    }
+
+    pub fn gram(&self) -> Option<&nalgebra::DMatrix<P>> {
+        self.gram.as_ref()
+    }
+}
+
+/// Buffers of the greedy solvers, reused across calls to avoid reallocations.
+///
+/// The buffers are resized on demand, so one workspace can serve several sensing matrices,
+/// unless it holds [`Precomputed`] matrices, which have to belong to the sensing matrix solved
+/// with.
+#[derive(Clone, Debug)]
+pub struct Workspace<P>
+where
+    P: Precision,
+{
+    pub(crate) precomputed: Option<Arc<Precomputed<P>>>,
+    pub(crate) residual: nalgebra::DVector<P>,
+    pub(crate) inner_products: nalgebra::DVector<P>,
+    // Aᴴy, for the Gram based correlation updates
+    pub(crate) adjoint_y: nalgebra::DVector<P>,
+    // orthonormal basis of the selected columns and its triangular factor, see OMP
+    pub(crate) q: nalgebra::DMatrix<P>,
+    pub(crate) r: nalgebra::DMatrix<P>,
+    pub(crate) projections: nalgebra::DVector<P>,
+    pub(crate) support: Vec<usize>,
+}
+
+impl<P> Workspace<P>
+where
+    P: Precision,
+{
+    pub fn new() -> Self {
+        Self {
+            precomputed: None,
+            residual: nalgebra::DVector::zeros(0),
+            inner_products: nalgebra::DVector::zeros(0),
+            adjoint_y: nalgebra::DVector::zeros(0),
+            q: nalgebra::DMatrix::zeros(0, 0),
+            r: nalgebra::DMatrix::zeros(0, 0),
+            projections: nalgebra::DVector::zeros(0),
+            support: Vec::new(),
+        }
+    }
+
+    pub fn with_precomputed(precomputed: Arc<Precomputed<P>>) -> Self {
+        Self {
+            precomputed: Some(precomputed),
+            ..Self::new()
+        }
+    }
+
+    /// Sizes the buffers for a `nrows`×`ncols` sensing matrix and up to `max_atoms` selected
+    /// columns, reallocating only if the shape changed.
+    pub(crate) fn prepare(&mut self, nrows: usize, ncols: usize, max_atoms: usize) {
+        debug_assert!(self
+            .precomputed
+            .as_ref()
+            .is_none_or(|p| p.adjoint.shape() == (ncols, nrows)));
+        if self.residual.len() != nrows {
+            self.residual = nalgebra::DVector::zeros(nrows);
+        }
+        if self.inner_products.len() != ncols {
+            self.inner_products = nalgebra::DVector::zeros(ncols);
+            self.adjoint_y = nalgebra::DVector::zeros(ncols);
+        }
+        if self.q.shape() != (nrows, max_atoms) {
+            self.q = nalgebra::DMatrix::zeros(nrows, max_atoms);
+            self.r = nalgebra::DMatrix::zeros(max_atoms, max_atoms);
+            self.projections = nalgebra::DVector::zeros(max_atoms);
+        }
+        self.support.clear();
+    }
+
+    /// Correlations Aᴴr of the sensing matrix columns with the residual.
+    pub(crate) fn correlate(&mut self, sensing_matrix: &nalgebra::DMatrix<P>) {
+        match &self.precomputed {
+            Some(precomputed) => precomputed
+                .adjoint
+                .mul_to(&self.residual, &mut self.inner_products),
+            None => sensing_matrix.ad_mul_to(&self.residual, &mut self.inner_products),
+        }
+    }
+}
+
+impl<P> Default for Workspace<P>
+where
+    P: Precision,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Workspaces for one sensing matrix, handed out to concurrent callers.
+#[derive(Debug)]
+pub(crate) struct WorkspacePool<P>
+where
+    P: Precision,
+{
+    precomputed: Option<Arc<Precomputed<P>>>,
+    idle: Mutex<Vec<Workspace<P>>>,
+}
+
+impl<P> WorkspacePool<P>
+where
+    P: Precision,
+{
+    pub(crate) fn new(precomputed: Option<Precomputed<P>>) -> Self {
+        Self {
+            precomputed: precomputed.map(Arc::new),
+            idle: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// An idle workspace, or a new one sharing the precomputed matrices. It returns to the
+    /// pool when dropped.
+    pub(crate) fn take(&self) -> PooledWorkspace<'_, P> {
+        // the idle list stays valid when a decode panics, so a poisoned lock is still used
+        let idle = self
+            .idle
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop();
+        let workspace = idle.unwrap_or_else(|| match &self.precomputed {
+            Some(precomputed) => Workspace::with_precomputed(precomputed.clone()),
+            None => Workspace::new(),
+        });
+        PooledWorkspace {
+            pool: self,
+            workspace: Some(workspace),
+        }
+    }
+}
+
+/// Workspace taken from a [`WorkspacePool`], given back on drop.
+pub(crate) struct PooledWorkspace<'a, P>
+where
+    P: Precision,
+{
+    pool: &'a WorkspacePool<P>,
+    workspace: Option<Workspace<P>>,
+}
+
+impl<P> Deref for PooledWorkspace<'_, P>
+where
+    P: Precision,
+{
+    type Target = Workspace<P>;
+
+    fn deref(&self) -> &Workspace<P> {
+        self.workspace.as_ref().unwrap()
+    }
+}
+
+impl<P> DerefMut for PooledWorkspace<'_, P>
+where
+    P: Precision,
+{
+    fn deref_mut(&mut self) -> &mut Workspace<P> {
+        self.workspace.as_mut().unwrap()
+    }
+}
+
+impl<P> Drop for PooledWorkspace<'_, P>
+where
+    P: Precision,
+{
+    fn drop(&mut self) {
+        if let Some(workspace) = self.workspace.take() {
+            self.pool
+                .idle
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(workspace);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        panic::AssertUnwindSafe,
+        sync::{Arc, PoisonError},
+    };
+
+    use approx::assert_relative_eq;
+    use nalgebra::{DMatrix, DVector};
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, Normal};
+
+    use super::{Precomputed, Workspace, WorkspacePool};
+    use crate::algorithm::{MatchingPursuitSolver, OrthogonalMatchingPursuitSolver};
+
+    fn problem() -> (DMatrix<f64>, DVector<f64>) {
+        let mut rng = StdRng::seed_from_u64(42);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let mut matrix = DMatrix::from_fn(32, 64, |_, _| normal.sample(&mut rng));
+        for mut column in matrix.column_iter_mut() {
+            column.normalize_mut();
+        }
+        let mut sparse = DVector::zeros(64);
+        for (idx, value) in [(3, 1.0), (17, -2.0), (40, 0.5), (63, 1.5)] {
+            sparse[idx] = value;
+        }
+        let compressed = &matrix * sparse;
+        (matrix, compressed)
+    }
+
+    #[test]
+    fn gram_updates_match_direct_correlations() {
+        let (matrix, compressed) = problem();
+        let mut workspace = Workspace::with_precomputed(Arc::new(Precomputed::new(&matrix, 64)));
+        assert!(workspace.precomputed.as_ref().unwrap().gram().is_some());
+
+        let omp = OrthogonalMatchingPursuitSolver::with_parameters(8, 1e-9);
+        assert_relative_eq!(
+            omp.solve_with_workspace(&compressed.column(0), &matrix, &mut workspace),
+            omp.solve(&compressed.column(0), &matrix),
+            epsilon = 1e-9
+        );
+
+        let mp = MatchingPursuitSolver::with_parameters(50, 1e-9);
+        assert_relative_eq!(
+            mp.solve_with_workspace(&compressed.column(0), &matrix, &mut workspace),
+            mp.solve(&compressed.column(0), &matrix),
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn gram_is_skipped_for_large_matrices() {
+        let (matrix, _) = problem();
+        let precomputed = Precomputed::new(&matrix, 32);
+
+        assert!(precomputed.gram().is_none());
+        assert_eq!(precomputed.adjoint(), &matrix.transpose());
+    }
+
+    #[test]
+    fn pooled_workspaces_are_given_back() {
+        let pool = WorkspacePool::<f64>::new(None);
+        let first = pool.take();
+        let second = pool.take();
+        assert!(pool.idle.lock().unwrap().is_empty());
+
+        drop(first);
+        drop(second);
+        assert_eq!(pool.idle.lock().unwrap().len(), 2);
+        let _reused = pool.take();
+        assert_eq!(pool.idle.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn poisoned_pool_keeps_working() {
+        let pool = WorkspacePool::<f64>::new(None);
+        drop(pool.take());
+        let _ = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            let _guard = pool.idle.lock().unwrap();
+            panic!("decode failed");
+        }));
+        assert!(pool.idle.is_poisoned());
+
+        let workspace = pool.take();
+        drop(workspace);
+        assert_eq!(
+            pool.idle
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .len(),
+            1
+        );
+    }
+}
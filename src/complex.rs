@@ -1,4 +1,4 @@
-use crate::precision::Precision;
+use crate::precision::{Complex64, Precision};
 
 pub trait ComplexFields {
     type RealField;
@@ -17,6 +17,165 @@ where
     }
 
     fn imag(&self) -> Vec<P::RealField> {
-        self.iter().map(|e| e.real()).collect()
+        self.iter().map(|e| e.imaginary()).collect()
+    }
+}
+
+/// Real values as complex ones with zero imaginary parts.
+pub fn from_real(values: &[f64]) -> Vec<Complex64> {
+    values.iter().map(|e| Complex64::new(*e, 0.0)).collect()
+}
+
+/// Real and imaginary parts of complex values.
+pub fn split(values: &[Complex64]) -> (Vec<f64>, Vec<f64>) {
+    values.iter().map(|e| (e.re, e.im)).unzip()
+}
+
+/// Inverse of [`split`], the parts must have the same length.
+pub fn merge(real: &[f64], imag: &[f64]) -> Vec<Complex64> {
+    assert_eq!(real.len(), imag.len(), "parts must have the same length");
+    real.iter()
+        .zip(imag)
+        .map(|(re, im)| Complex64::new(*re, *im))
+        .collect()
+}
+
+/// Magnitudes and phases in (-π, π] of complex values.
+pub fn to_polar(values: &[Complex64]) -> (Vec<f64>, Vec<f64>) {
+    values.iter().map(|e| e.to_polar()).unzip()
+}
+
+/// Inverse of [`to_polar`], the magnitudes and phases must have the same length.
+pub fn from_polar(magnitudes: &[f64], phases: &[f64]) -> Vec<Complex64> {
+    assert_eq!(
+        magnitudes.len(),
+        phases.len(),
+        "magnitudes and phases must have the same length"
+    );
+    magnitudes
+        .iter()
+        .zip(phases)
+        .map(|(r, theta)| Complex64::from_polar(*r, *theta))
+        .collect()
+}
+
+/// Packs the spectrum of a real signal, which is Hermitian symmetric (`X[n-k] = conj(X[k])`),
+/// into as many real values as samples: `X[0]`, the real and imaginary parts of `X[1]` up to
+/// `X[(n-1)/2]`, and the real `X[n/2]` for even lengths.
+///
+/// The redundant half and the imaginary parts which are zero for real signals are dropped.
+pub fn pack_hermitian(spectrum: &[Complex64]) -> Vec<f64> {
+    let len = spectrum.len();
+    let mut packed = Vec::with_capacity(len);
+    if len == 0 {
+        return packed;
+    }
+    packed.push(spectrum[0].re);
+    for value in &spectrum[1..len.div_ceil(2)] {
+        packed.push(value.re);
+        packed.push(value.im);
+    }
+    if len.is_multiple_of(2) {
+        packed.push(spectrum[len / 2].re);
+    }
+    packed
+}
+
+/// Inverse of [`pack_hermitian`], restoring the full Hermitian symmetric spectrum.
+pub fn unpack_hermitian(packed: &[f64]) -> Vec<Complex64> {
+    let len = packed.len();
+    let mut spectrum = vec![Complex64::new(0.0, 0.0); len];
+    if len == 0 {
+        return spectrum;
+    }
+    spectrum[0] = Complex64::new(packed[0], 0.0);
+    for k in 1..len.div_ceil(2) {
+        let value = Complex64::new(packed[2 * k - 1], packed[2 * k]);
+        spectrum[k] = value;
+        spectrum[len - k] = value.conj();
+    }
+    if len.is_multiple_of(2) {
+        spectrum[len / 2] = Complex64::new(packed[len - 1], 0.0);
+    }
+    spectrum
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use rustfft::FftDirection;
+
+    use super::{
+        from_polar, from_real, merge, pack_hermitian, split, to_polar, unpack_hermitian,
+        ComplexFields,
+    };
+    use crate::{planner, precision::Complex64};
+
+    fn values() -> Vec<Complex64> {
+        vec![
+            Complex64::new(1.0, 2.0),
+            Complex64::new(-3.0, 0.5),
+            Complex64::new(0.0, -1.0),
+        ]
+    }
+
+    // spectrum of a real signal, via the FFT
+    fn real_spectrum(signal: &[f64]) -> Vec<Complex64> {
+        let mut spectrum = from_real(signal);
+        planner::shared()
+            .fft(signal.len(), FftDirection::Forward)
+            .process(&mut spectrum);
+        spectrum
+    }
+
+    #[test]
+    fn real_and_imaginary_parts() {
+        let values = values();
+        assert_eq!(values.real(), vec![1.0, -3.0, 0.0]);
+        assert_eq!(values.imag(), vec![2.0, 0.5, -1.0]);
+        assert_eq!(vec![1.5, -2.0].imag(), vec![0.0, 0.0]);
+        assert_eq!(from_real(&[1.5, -2.0]).imag(), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn split_and_merge() {
+        let (real, imag) = split(&values());
+        assert_eq!(real, vec![1.0, -3.0, 0.0]);
+        assert_eq!(imag, vec![2.0, 0.5, -1.0]);
+        assert_eq!(merge(&real, &imag), values());
+    }
+
+    #[test]
+    fn polar_round_trip() {
+        let (magnitudes, phases) = to_polar(&values());
+        assert_relative_eq!(magnitudes[0], 5.0f64.sqrt());
+        assert_relative_eq!(magnitudes[2], 1.0);
+        assert_relative_eq!(phases[2], -std::f64::consts::FRAC_PI_2);
+
+        for (value, expected) in from_polar(&magnitudes, &phases).iter().zip(values()) {
+            assert_relative_eq!(value.re, expected.re, epsilon = 1e-12);
+            assert_relative_eq!(value.im, expected.im, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn hermitian_packing() {
+        for signal in [
+            vec![1.0, -2.0, 0.5, 3.0, 0.0, 1.5],
+            vec![0.3, 2.0, -1.0, 4.0, -0.5],
+        ] {
+            let spectrum = real_spectrum(&signal);
+            let packed = pack_hermitian(&spectrum);
+            assert_eq!(packed.len(), signal.len());
+
+            let unpacked = unpack_hermitian(&packed);
+            assert_eq!(unpacked.len(), spectrum.len());
+            for (value, expected) in unpacked.iter().zip(&spectrum) {
+                assert_relative_eq!(value.re, expected.re, epsilon = 1e-12);
+                assert_relative_eq!(value.im, expected.im, epsilon = 1e-12);
+            }
+        }
+        assert!(pack_hermitian(&[]).is_empty());
+        assert!(unpack_hermitian(&[]).is_empty());
     }
 }
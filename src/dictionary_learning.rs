@@ -0,0 +1,288 @@
+use std::sync::Arc;
+
+use nalgebra::{DMatrix, DVector, DVectorView};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::StandardNormal;
+
+use crate::{
+    algorithm::{OrthogonalMatchingPursuitSolver, Precomputed, Workspace},
+    matrix::RealMatrix,
+};
+
+// atoms more coherent than this with an earlier atom are treated as duplicates
+const DUPLICATE_COHERENCE: f64 = 0.99;
+
+/// Update of the atoms, alternating with the sparse coding of the training frames.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DictionaryUpdate {
+    /// K-SVD, refits each atom together with its coefficients by a rank one approximation
+    /// of the residual of the frames using it
+    KSvd,
+    /// method of optimal directions, least squares fit of all atoms to the current coefficients
+    Mod,
+}
+
+/// Trains an overcomplete synthesis dictionary in which example frames are sparse.
+///
+/// The frames are sparse coded with orthogonal matching pursuit, the atoms are then updated
+/// with K-SVD or MOD. The learned dictionary has unit norm atoms and plugs into the model as
+/// [`Transformation::Learned`](crate::Transformation::Learned).
+pub struct DictionaryLearning {
+    atoms: usize,
+    sparsity: usize,
+    iterations: usize,
+    tolerance: f64,
+    update: DictionaryUpdate,
+    seed: u64,
+}
+
+impl DictionaryLearning {
+    /// Dictionary with `atoms` columns, representing each frame with at most `sparsity` atoms.
+    pub fn new(atoms: usize, sparsity: usize) -> Self {
+        Self {
+            atoms,
+            sparsity,
+            iterations: 20,
+            tolerance: 1e-9,
+            update: DictionaryUpdate::KSvd,
+            seed: 0,
+        }
+    }
+
+    pub fn with_update(&mut self, update: DictionaryUpdate) -> &mut Self {
+        self.update = update;
+        self
+    }
+
+    pub fn with_iterations(&mut self, iterations: usize) -> &mut Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Residual tolerance of the sparse coding step.
+    pub fn with_tolerance(&mut self, tolerance: f64) -> &mut Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Seed for picking the initial atoms among the frames.
+    pub fn with_seed(&mut self, seed: u64) -> &mut Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Learns a `frame length`×`atoms` dictionary from frames of equal length.
+    pub fn train<T>(&self, frames: &[T]) -> RealMatrix
+    where
+        T: AsRef<[f64]>,
+    {
+        assert!(
+            !frames.is_empty(),
+            "dictionary learning needs training frames"
+        );
+        let len = frames[0].as_ref().len();
+        assert!(
+            frames.iter().all(|f| f.as_ref().len() == len),
+            "training frames must have equal length"
+        );
+        let signals = DMatrix::from_fn(len, frames.len(), |i, j| frames[j].as_ref()[i]);
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let initial: Vec<usize> = (0..self.atoms)
+            .map(|_| rng.gen_range(0..signals.ncols()))
+            .collect();
+        let mut dictionary = signals.select_columns(&initial);
+        for mut atom in dictionary.column_iter_mut() {
+            if atom.norm() <= f64::EPSILON {
+                atom.copy_from(&random_direction(len, &mut rng));
+            }
+            atom.normalize_mut();
+        }
+
+        for _ in 0..self.iterations {
+            let mut coefficients = self.sparse_code(&signals, &dictionary);
+            match self.update {
+                DictionaryUpdate::KSvd => ksvd_update(&signals, &mut dictionary, &mut coefficients),
+                DictionaryUpdate::Mod => mod_update(&signals, &mut dictionary, &coefficients),
+            }
+            let mut residual = &signals - &dictionary * &coefficients;
+            replace_degenerate_atoms(&mut dictionary, &coefficients, &mut residual, &mut rng);
+        }
+        dictionary
+    }
+
+    /// Sparse codes the frames, given as columns, in the dictionary, one column per frame.
+    pub fn sparse_code(&self, signals: &RealMatrix, dictionary: &RealMatrix) -> RealMatrix {
+        let solver =
+            OrthogonalMatchingPursuitSolver::with_parameters(self.sparsity, self.tolerance);
+        let precomputed = Arc::new(Precomputed::new(dictionary, dictionary.ncols()));
+        let mut workspace = Workspace::with_precomputed(precomputed);
+
+        let mut coefficients = DMatrix::zeros(dictionary.ncols(), signals.ncols());
+        for (frame, mut x) in signals.column_iter().zip(coefficients.column_iter_mut()) {
+            let y = DVectorView::from_slice(frame.as_slice(), frame.len());
+            x.copy_from(&solver.solve_with_workspace(&y, dictionary, &mut workspace));
+        }
+        coefficients
+    }
+}
+
+// refits one atom at a time to the residual of the frames using it, keeping the support
+fn ksvd_update(signals: &RealMatrix, dictionary: &mut RealMatrix, coefficients: &mut RealMatrix) {
+    let mut residual = signals - &*dictionary * &*coefficients;
+    for k in 0..dictionary.ncols() {
+        let users: Vec<usize> = (0..signals.ncols())
+            .filter(|&i| coefficients[(k, i)] != 0.0)
+            .collect();
+        if users.is_empty() {
+            continue;
+        }
+
+        // residual without the contribution of atom k, restricted to its users
+        let atom = dictionary.column(k).clone_owned();
+        let mut restricted = residual.select_columns(&users);
+        for (col, &i) in users.iter().enumerate() {
+            restricted
+                .column_mut(col)
+                .axpy(coefficients[(k, i)], &atom, 1.0);
+        }
+
+        let svd = restricted.clone().svd(true, true);
+        let best = svd.singular_values.imax();
+        let u = svd.u.as_ref().expect("left singular vectors").column(best);
+        let v_t = svd.v_t.as_ref().expect("right singular vectors").row(best);
+        let sigma = svd.singular_values[best];
+
+        dictionary.set_column(k, &u);
+        for (col, &i) in users.iter().enumerate() {
+            let coefficient = sigma * v_t[col];
+            coefficients[(k, i)] = coefficient;
+            let mut r = restricted.column(col).clone_owned();
+            r.axpy(-coefficient, &u, 1.0);
+            residual.set_column(i, &r);
+        }
+    }
+}
+
+// least squares fit of all atoms, D = Y X⁺, unused atoms become zero
+fn mod_update(signals: &RealMatrix, dictionary: &mut RealMatrix, coefficients: &RealMatrix) {
+    let pseudo_inverse = coefficients
+        .clone()
+        .pseudo_inverse(1e-12)
+        .expect("pseudo inverse of the coefficients");
+    *dictionary = signals * pseudo_inverse;
+    for mut atom in dictionary.column_iter_mut() {
+        let norm = atom.norm();
+        if norm > f64::EPSILON {
+            atom.unscale_mut(norm);
+        }
+    }
+}
+
+// unused atoms and near duplicates of other atoms would stay stuck, they are replaced by the
+// worst represented frames (or random directions once all frames are represented)
+fn replace_degenerate_atoms(
+    dictionary: &mut RealMatrix,
+    coefficients: &RealMatrix,
+    residual: &mut RealMatrix,
+    rng: &mut StdRng,
+) {
+    for j in 0..dictionary.ncols() {
+        let unused = coefficients.row(j).iter().all(|c| *c == 0.0);
+        let atom = dictionary.column(j);
+        let duplicate = (0..j).any(|i| dictionary.column(i).dot(&atom).abs() > DUPLICATE_COHERENCE);
+        if !unused && !duplicate {
+            continue;
+        }
+
+        let norms: Vec<f64> = residual.column_iter().map(|c| c.norm()).collect();
+        let worst = (0..norms.len()).fold(0, |w, i| if norms[i] > norms[w] { i } else { w });
+        let replacement = if norms[worst] > f64::EPSILON {
+            let frame = residual.column(worst).normalize();
+            // the same frame should not replace two atoms
+            residual.column_mut(worst).fill(0.0);
+            frame
+        } else {
+            random_direction(dictionary.nrows(), rng).normalize()
+        };
+        dictionary.set_column(j, &replacement);
+    }
+}
+
+fn random_direction(len: usize, rng: &mut StdRng) -> DVector<f64> {
+    DVector::from_fn(len, |_, _| rng.sample(StandardNormal))
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use nalgebra::{DMatrix, DVector};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use rand_distr::StandardNormal;
+
+    use super::{DictionaryLearning, DictionaryUpdate};
+
+    const LEN: usize = 12;
+    const ATOMS: usize = 20;
+    const K: usize = 2;
+    const FRAMES: usize = 300;
+
+    fn random_dictionary(rng: &mut StdRng) -> DMatrix<f64> {
+        let mut dictionary = DMatrix::from_fn(LEN, ATOMS, |_, _| rng.sample(StandardNormal));
+        for mut atom in dictionary.column_iter_mut() {
+            atom.normalize_mut();
+        }
+        dictionary
+    }
+
+    fn training_frames(dictionary: &DMatrix<f64>, rng: &mut StdRng) -> Vec<Vec<f64>> {
+        (0..FRAMES)
+            .map(|_| {
+                let mut frame = DVector::zeros(LEN);
+                for _ in 0..K {
+                    let atom = rng.gen_range(0..ATOMS);
+                    let amplitude: f64 = rng.sample(StandardNormal);
+                    frame.axpy(amplitude, &dictionary.column(atom), 1.0);
+                }
+                frame.data.into()
+            })
+            .collect()
+    }
+
+    // fraction of the generating atoms matched by a learned atom
+    fn recovered_atoms(truth: &DMatrix<f64>, learned: &DMatrix<f64>) -> f64 {
+        let correlations = truth.transpose() * learned;
+        let recovered = correlations
+            .row_iter()
+            .filter(|row| row.iter().any(|c| c.abs() > 0.99))
+            .count();
+        recovered as f64 / truth.ncols() as f64
+    }
+
+    #[test]
+    fn recovers_generating_dictionary() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let truth = random_dictionary(&mut rng);
+        let frames = training_frames(&truth, &mut rng);
+        let signals = DMatrix::from_fn(LEN, FRAMES, |i, j| frames[j][i]);
+
+        for update in [DictionaryUpdate::KSvd, DictionaryUpdate::Mod] {
+            let mut learning = DictionaryLearning::new(ATOMS, K);
+            let learned = learning
+                .with_update(update)
+                .with_iterations(20)
+                .train(&frames);
+
+            assert_eq!(learned.shape(), (LEN, ATOMS));
+            for atom in learned.column_iter() {
+                assert_relative_eq!(atom.norm(), 1.0, epsilon = 1e-9);
+            }
+            let recovered = recovered_atoms(&truth, &learned);
+            assert!(recovered >= 0.7, "{update:?} recovered {recovered}");
+
+            let coefficients = learning.sparse_code(&signals, &learned);
+            let error = (&signals - &learned * coefficients).norm() / signals.norm();
+            assert!(error < 0.25, "{update:?} representation error {error}");
+        }
+    }
+}
@@ -0,0 +1,395 @@
+use derive_more::Display;
+
+use crate::quantization::{
+    BitReader, BitWriter, QuantizationRange, QuantizedMeasurements, Quantizer,
+};
+
+const MAGIC: [u8; 2] = *b"SM";
+const VERSION: u8 = 2;
+// magic, version, quantizer kind, bits per measurement, number of measurements (u32),
+// range (f64), µ (f64, zero for uniform quantization), payload length in bytes (u32)
+const HEADER_LEN: usize = 2 + 1 + 1 + 1 + 4 + 8 + 8 + 4;
+
+const UNIFORM: u8 = 0;
+const MU_LAW: u8 = 1;
+
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    #[display(fmt = "stream ends before its header or payload")]
+    Truncated,
+    #[display(fmt = "stream does not start with a valid header")]
+    InvalidHeader,
+    #[display(fmt = "unsupported stream version {}", _0)]
+    UnsupportedVersion(u8),
+    #[display(fmt = "stream holds {} measurements, at most {} are expected", _0, _1)]
+    TooManyMeasurements(usize, usize),
+    #[display(fmt = "stream holds {} measurements, the model expects {}", _0, _1)]
+    MeasurementCount(usize, usize),
+    #[display(fmt = "stream was quantized differently than expected by the model")]
+    QuantizerMismatch,
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Entropy codes quantized measurements with an adaptive arithmetic coder.
+///
+/// The byte stream starts with a header containing the quantizer and its parameters,
+/// followed by the arithmetic coded cell indices.
+pub fn encode(quantizer: &Quantizer, quantized: &QuantizedMeasurements) -> Vec<u8> {
+    assert_eq!(
+        quantizer.bits(),
+        quantized.bits(),
+        "measurements were quantized with a different quantizer"
+    );
+    let (kind, mu) = match quantizer {
+        Quantizer::Uniform { .. } => (UNIFORM, 0.0),
+        Quantizer::MuLaw { mu, .. } => (MU_LAW, *mu),
+    };
+    let len =
+        u32::try_from(quantized.len()).expect("the header holds at most u32::MAX measurements");
+
+    let mut model = FrequencyModel::new(1 << quantized.bits());
+    let mut encoder = ArithmeticEncoder::new();
+    for symbol in quantized.indices() {
+        encoder.encode(&model, symbol);
+        model.update(symbol);
+    }
+    let payload = encoder.finish();
+    let payload_len =
+        u32::try_from(payload.len()).expect("the header holds payloads of at most u32::MAX bytes");
+
+    let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+    bytes.extend_from_slice(&MAGIC);
+    bytes.push(VERSION);
+    bytes.push(kind);
+    bytes.push(quantized.bits());
+    bytes.extend_from_slice(&len.to_le_bytes());
+    bytes.extend_from_slice(&quantized.range().to_le_bytes());
+    bytes.extend_from_slice(&mu.to_le_bytes());
+    bytes.extend_from_slice(&payload_len.to_le_bytes());
+    bytes.extend(payload);
+    bytes
+}
+
+/// Inverse of [`encode`], returning the quantizer of the header (with the transmitted range)
+/// and the quantized measurements.
+///
+/// The stream is untrusted input, streams with more than `max_len` measurements are rejected
+/// before decoding them.
+pub fn decode(
+    bytes: &[u8],
+    max_len: usize,
+) -> Result<(Quantizer, QuantizedMeasurements), DecodeError> {
+    if bytes.len() < 3 {
+        return Err(DecodeError::Truncated);
+    }
+    if bytes[0..2] != MAGIC {
+        return Err(DecodeError::InvalidHeader);
+    }
+    if bytes[2] != VERSION {
+        return Err(DecodeError::UnsupportedVersion(bytes[2]));
+    }
+    if bytes.len() < HEADER_LEN {
+        return Err(DecodeError::Truncated);
+    }
+    let bits = bytes[4];
+    let len = u32::from_le_bytes(bytes[5..9].try_into().unwrap()) as usize;
+    let range = f64::from_le_bytes(bytes[9..17].try_into().unwrap());
+    let mu = f64::from_le_bytes(bytes[17..25].try_into().unwrap());
+    let payload_len = u32::from_le_bytes(bytes[25..29].try_into().unwrap()) as usize;
+
+    // the same ranges as accepted by Quantizer::quantize
+    if !(1..=16).contains(&bits) || !range.is_finite() || range <= 0.0 {
+        return Err(DecodeError::InvalidHeader);
+    }
+    let quantizer = match bytes[3] {
+        UNIFORM => Quantizer::Uniform {
+            bits,
+            range: QuantizationRange::Fixed(range),
+        },
+        MU_LAW if mu.is_finite() && mu > 0.0 => Quantizer::MuLaw {
+            bits,
+            range: QuantizationRange::Fixed(range),
+            mu,
+        },
+        _ => return Err(DecodeError::InvalidHeader),
+    };
+    if len > max_len {
+        return Err(DecodeError::TooManyMeasurements(len, max_len));
+    }
+    let payload = &bytes[HEADER_LEN..];
+    if payload.len() < payload_len {
+        return Err(DecodeError::Truncated);
+    }
+    if payload.len() > payload_len {
+        return Err(DecodeError::InvalidHeader);
+    }
+
+    let mut model = FrequencyModel::new(1 << bits);
+    let mut decoder = ArithmeticDecoder::new(payload);
+    let mut writer = BitWriter::default();
+    for _ in 0..len {
+        let symbol = decoder.decode(&model);
+        model.update(symbol);
+        writer.write(symbol as u32, bits);
+    }
+    // the decoder looks ahead by one state, reading further means the payload ran out
+    if decoder.input.position() > payload.len() * 8 + STATE_BITS as usize {
+        return Err(DecodeError::Truncated);
+    }
+
+    Ok((
+        quantizer,
+        QuantizedMeasurements::from_bytes(writer.into_bytes(), len, bits, range),
+    ))
+}
+
+/// Achieved bits per sample of the original signal, including the header.
+pub fn bits_per_sample(encoded: &[u8], samples: usize) -> f64 {
+    (encoded.len() * 8) as f64 / samples as f64
+}
+
+const STATE_BITS: u32 = 32;
+const FULL_RANGE: u64 = 1 << STATE_BITS;
+const HALF_RANGE: u64 = FULL_RANGE >> 1;
+const QUARTER_RANGE: u64 = HALF_RANGE >> 1;
+const STATE_MASK: u64 = FULL_RANGE - 1;
+
+// frequencies are halved once their total exceeds this, must stay below QUARTER_RANGE
+const MAX_TOTAL: u64 = 1 << 24;
+const INCREMENT: u64 = 32;
+
+struct FrequencyModel {
+    frequencies: Vec<u64>,
+    total: u64,
+}
+
+impl FrequencyModel {
+    fn new(symbols: usize) -> Self {
+        Self {
+            frequencies: vec![1; symbols],
+            total: symbols as u64,
+        }
+    }
+
+    // cumulative frequency of all symbols below `symbol`
+    fn low(&self, symbol: usize) -> u64 {
+        self.frequencies[..symbol].iter().sum()
+    }
+
+    fn update(&mut self, symbol: usize) {
+        self.frequencies[symbol] += INCREMENT;
+        self.total += INCREMENT;
+        if self.total > MAX_TOTAL {
+            self.frequencies
+                .iter_mut()
+                .for_each(|f| *f = (*f).div_ceil(2));
+            self.total = self.frequencies.iter().sum();
+        }
+    }
+}
+
+struct ArithmeticEncoder {
+    low: u64,
+    high: u64,
+    underflow: usize,
+    output: BitWriter,
+}
+
+impl ArithmeticEncoder {
+    fn new() -> Self {
+        Self {
+            low: 0,
+            high: STATE_MASK,
+            underflow: 0,
+            output: BitWriter::default(),
+        }
+    }
+
+    fn encode(&mut self, model: &FrequencyModel, symbol: usize) {
+        let range = self.high - self.low + 1;
+        let symbol_low = model.low(symbol);
+        let symbol_high = symbol_low + model.frequencies[symbol];
+        self.high = self.low + symbol_high * range / model.total - 1;
+        self.low += symbol_low * range / model.total;
+
+        while (self.low ^ self.high) & HALF_RANGE == 0 {
+            let bit = (self.low >> (STATE_BITS - 1)) as u32;
+            self.output.write(bit, 1);
+            for _ in 0..self.underflow {
+                self.output.write(bit ^ 1, 1);
+            }
+            self.underflow = 0;
+            self.low = (self.low << 1) & STATE_MASK;
+            self.high = ((self.high << 1) & STATE_MASK) | 1;
+        }
+        while self.low & !self.high & QUARTER_RANGE != 0 {
+            self.underflow += 1;
+            self.low = (self.low << 1) ^ HALF_RANGE;
+            self.high = ((self.high ^ HALF_RANGE) << 1) | HALF_RANGE | 1;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        // disambiguates the final interval, the decoder reads zeros past the end
+        self.output.write(1, 1);
+        self.output.into_bytes()
+    }
+}
+
+struct ArithmeticDecoder<'a> {
+    low: u64,
+    high: u64,
+    code: u64,
+    input: BitReader<'a>,
+}
+
+impl<'a> ArithmeticDecoder<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        let mut input = BitReader::new(bytes);
+        let code = (0..STATE_BITS).fold(0, |code, _| (code << 1) | input.read(1) as u64);
+        Self {
+            low: 0,
+            high: STATE_MASK,
+            code,
+            input,
+        }
+    }
+
+    fn decode(&mut self, model: &FrequencyModel) -> usize {
+        let range = self.high - self.low + 1;
+        let offset = self.code - self.low;
+        let value = ((offset + 1) * model.total - 1) / range;
+
+        let mut symbol_low = 0;
+        let mut symbol = 0;
+        while symbol_low + model.frequencies[symbol] <= value {
+            symbol_low += model.frequencies[symbol];
+            symbol += 1;
+        }
+        let symbol_high = symbol_low + model.frequencies[symbol];
+        self.high = self.low + symbol_high * range / model.total - 1;
+        self.low += symbol_low * range / model.total;
+
+        while (self.low ^ self.high) & HALF_RANGE == 0 {
+            self.code = ((self.code << 1) & STATE_MASK) | self.input.read(1) as u64;
+            self.low = (self.low << 1) & STATE_MASK;
+            self.high = ((self.high << 1) & STATE_MASK) | 1;
+        }
+        while self.low & !self.high & QUARTER_RANGE != 0 {
+            self.code = (self.code & HALF_RANGE)
+                | ((self.code << 1) & (STATE_MASK >> 1))
+                | self.input.read(1) as u64;
+            self.low = (self.low << 1) ^ HALF_RANGE;
+            self.high = ((self.high ^ HALF_RANGE) << 1) | HALF_RANGE | 1;
+        }
+        symbol
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{bits_per_sample, decode, encode, DecodeError};
+    use crate::quantization::{BitWriter, QuantizationRange, QuantizedMeasurements, Quantizer};
+
+    fn quantizer(bits: u8) -> Quantizer {
+        Quantizer::Uniform {
+            bits,
+            range: QuantizationRange::Fixed(1.5),
+        }
+    }
+
+    fn quantized(indices: &[usize], bits: u8) -> QuantizedMeasurements {
+        let mut writer = BitWriter::default();
+        for idx in indices {
+            writer.write(*idx as u32, bits);
+        }
+        QuantizedMeasurements::from_bytes(writer.into_bytes(), indices.len(), bits, 1.5)
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for bits in [1, 4, 10] {
+            let indices: Vec<usize> = (0..1000).map(|_| rng.gen_range(0..1 << bits)).collect();
+            let original = quantized(&indices, bits);
+
+            let decoded = decode(&encode(&quantizer(bits), &original), 1000).unwrap();
+            assert_eq!(decoded, (quantizer(bits), original));
+        }
+    }
+
+    #[test]
+    fn header_holds_the_quantizer() {
+        let mu_law = Quantizer::MuLaw {
+            bits: 3,
+            range: QuantizationRange::Adaptive,
+            mu: 255.0,
+        };
+        let (decoded, _) = decode(&encode(&mu_law, &quantized(&[1, 7], 3)), 2).unwrap();
+        assert_eq!(
+            decoded,
+            Quantizer::MuLaw {
+                bits: 3,
+                range: QuantizationRange::Fixed(1.5),
+                mu: 255.0,
+            }
+        );
+    }
+
+    #[test]
+    fn skewed_symbols_need_fewer_bits() {
+        let mut rng = StdRng::seed_from_u64(42);
+        // mostly the center cell, as for measurements concentrated around zero
+        let indices: Vec<usize> = (0..4096)
+            .map(|_| {
+                if rng.gen_bool(0.9) {
+                    8
+                } else {
+                    rng.gen_range(0..16)
+                }
+            })
+            .collect();
+
+        let encoded = encode(&quantizer(4), &quantized(&indices, 4));
+        assert!(bits_per_sample(&encoded, indices.len()) < 1.5);
+    }
+
+    #[test]
+    fn rejects_invalid_streams() {
+        let encoded = encode(&quantizer(2), &quantized(&[1, 2, 3], 2));
+
+        assert_eq!(decode(&encoded[..10], 3), Err(DecodeError::Truncated));
+        assert_eq!(
+            decode(&encoded[..encoded.len() - 1], 3),
+            Err(DecodeError::Truncated)
+        );
+        let mut invalid = encoded.clone();
+        invalid[0] = b'X';
+        assert_eq!(decode(&invalid, 3), Err(DecodeError::InvalidHeader));
+        let mut version = encoded.clone();
+        version[2] = 9;
+        assert_eq!(decode(&version, 3), Err(DecodeError::UnsupportedVersion(9)));
+        let mut range = encoded.clone();
+        range[9..17].copy_from_slice(&f64::NAN.to_le_bytes());
+        assert_eq!(decode(&range, 3), Err(DecodeError::InvalidHeader));
+        range[9..17].copy_from_slice(&0.0f64.to_le_bytes());
+        assert_eq!(decode(&range, 3), Err(DecodeError::InvalidHeader));
+    }
+
+    #[test]
+    fn rejects_untrusted_lengths() {
+        let encoded = encode(&quantizer(2), &quantized(&[1, 2, 3], 2));
+        assert_eq!(
+            decode(&encoded, 2),
+            Err(DecodeError::TooManyMeasurements(3, 2))
+        );
+
+        // header claiming far more measurements than its payload holds
+        let mut forged = encoded;
+        forged[5..9].copy_from_slice(&1000u32.to_le_bytes());
+        assert_eq!(decode(&forged, 1000), Err(DecodeError::Truncated));
+    }
+}
@@ -0,0 +1,238 @@
+use std::sync::Arc;
+
+use nalgebra::DMatrix;
+use rustfft::{num_complex::Complex64, Fft, FftDirection};
+
+use crate::{matrix::ComplexMatrix, planner};
+
+/// Analysis window of a [`GaborFrame`], periodic so that overlapping windows add up evenly.
+///
+/// Smooth windows concentrate the atoms in frequency, at the cost of a higher coherence
+/// between neighbouring bins, which greedy solvers only tolerate for very sparse signals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Window {
+    Rectangular,
+    Hann,
+    Hamming,
+}
+
+impl Window {
+    /// Samples of the window of length `len`.
+    pub fn coefficients(self, len: usize) -> Vec<f64> {
+        let phase = |m: usize| 2.0 * std::f64::consts::PI * m as f64 / len as f64;
+        (0..len)
+            .map(|m| match self {
+                Window::Rectangular => 1.0,
+                Window::Hann => 0.5 - 0.5 * phase(m).cos(),
+                Window::Hamming => 0.54 - 0.46 * phase(m).cos(),
+            })
+            .collect()
+    }
+}
+
+/// Gabor frame, i.e. the atoms of a short-time Fourier transform: windowed complex
+/// exponentials shifted in time by the hop size and in frequency by the FFT bins.
+///
+/// The signal is treated as periodic, there is one time frame every `hop` samples and each
+/// frame has `fft_size` frequency bins. The coefficients are ordered frame by frame, the atom
+/// of frame `t` and bin `k` has index `t * fft_size + k`. All atoms have unit norm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GaborFrame {
+    window: Window,
+    window_len: usize,
+    hop: usize,
+    fft_size: usize,
+}
+
+impl GaborFrame {
+    /// Hann windows of `window_len` samples every `hop` samples, with as many bins as samples.
+    pub fn with_parameters(window_len: usize, hop: usize) -> GaborFrame {
+        assert!(window_len > 0, "window length must be positive");
+        assert!(hop > 0, "hop must be positive");
+        GaborFrame {
+            window: Window::Hann,
+            window_len,
+            hop,
+            fft_size: window_len,
+        }
+    }
+
+    pub fn with_window(self, window: Window) -> GaborFrame {
+        GaborFrame { window, ..self }
+    }
+
+    /// Number of frequency bins, zero padding the windows if larger than the window length.
+    pub fn with_fft_size(self, fft_size: usize) -> GaborFrame {
+        assert!(
+            fft_size >= self.window_len,
+            "the FFT size must not be smaller than the window"
+        );
+        GaborFrame { fft_size, ..self }
+    }
+
+    /// Number of time frames for a signal of length `len`.
+    pub fn frames(&self, len: usize) -> usize {
+        assert!(
+            len.is_multiple_of(self.hop) && self.window_len <= len,
+            "the signal length must be a multiple of the hop and at least the window length"
+        );
+        len / self.hop
+    }
+
+    /// Number of coefficients for a signal of length `len`.
+    pub fn coefficient_len(&self, len: usize) -> usize {
+        self.frames(len) * self.fft_size
+    }
+
+    /// Dense synthesis matrix for signals of length `len`, with one atom per column.
+    /// Its size grows quadratically, see [`GaborFrame::operator`] for larger signals.
+    pub fn synthesis_matrix(&self, len: usize) -> ComplexMatrix {
+        let window = self.normalized_window();
+        let mut matrix = DMatrix::zeros(len, self.coefficient_len(len));
+        for t in 0..self.frames(len) {
+            for k in 0..self.fft_size {
+                let mut atom = matrix.column_mut(t * self.fft_size + k);
+                for (m, g) in window.iter().enumerate() {
+                    let phase = 2.0 * std::f64::consts::PI * (k * m % self.fft_size) as f64
+                        / self.fft_size as f64;
+                    atom[(t * self.hop + m) % len] = Complex64::from_polar(*g, phase);
+                }
+            }
+        }
+        matrix
+    }
+
+    /// Fast operator for signals of length `len`, applying the frame with FFTs.
+    pub fn operator(&self, len: usize) -> GaborOperator {
+        let planner = planner::shared();
+        GaborOperator {
+            frame: *self,
+            len,
+            window: self.normalized_window(),
+            forward: planner.fft(self.fft_size, FftDirection::Forward),
+            inverse: planner.fft(self.fft_size, FftDirection::Inverse),
+        }
+    }
+
+    // window scaled to unit norm, which is also the norm of each atom
+    fn normalized_window(&self) -> Vec<f64> {
+        let window = self.window.coefficients(self.window_len);
+        let norm = window.iter().map(|g| g * g).sum::<f64>().sqrt();
+        window.iter().map(|g| g / norm).collect()
+    }
+}
+
+/// [`GaborFrame`] for a fixed signal length, applied with one FFT per time frame instead of a
+/// dense matrix product.
+///
+/// The operator is standalone, e.g. to analyze signals or synthesize decompressed coefficients
+/// of large frames. Models use the dense [`GaborFrame::synthesis_matrix`], as their solvers
+/// work on the explicit sensing matrix.
+#[derive(Clone)]
+pub struct GaborOperator {
+    frame: GaborFrame,
+    len: usize,
+    window: Vec<f64>,
+    forward: Arc<dyn Fft<f64>>,
+    inverse: Arc<dyn Fft<f64>>,
+}
+
+impl GaborOperator {
+    /// Signal of the coefficients, i.e. the synthesis matrix applied to them.
+    pub fn synthesize(&self, coefficients: &[Complex64]) -> Vec<Complex64> {
+        let fft_size = self.frame.fft_size;
+        assert_eq!(coefficients.len(), self.frame.coefficient_len(self.len));
+
+        let mut signal = vec![Complex64::new(0.0, 0.0); self.len];
+        let mut buffer = vec![Complex64::new(0.0, 0.0); fft_size];
+        for (t, frame) in coefficients.chunks(fft_size).enumerate() {
+            buffer.copy_from_slice(frame);
+            self.inverse.process(&mut buffer);
+            for (m, g) in self.window.iter().enumerate() {
+                signal[(t * self.frame.hop + m) % self.len] += buffer[m] * g;
+            }
+        }
+        signal
+    }
+
+    /// Coefficients of the signal, i.e. the adjoint of the synthesis matrix applied to it.
+    pub fn analyze(&self, signal: &[Complex64]) -> Vec<Complex64> {
+        let fft_size = self.frame.fft_size;
+        assert_eq!(signal.len(), self.len);
+
+        let mut coefficients = vec![Complex64::new(0.0, 0.0); self.frame.coefficient_len(self.len)];
+        for (t, frame) in coefficients.chunks_mut(fft_size).enumerate() {
+            for (m, g) in self.window.iter().enumerate() {
+                frame[m] = signal[(t * self.frame.hop + m) % self.len] * g;
+            }
+            self.forward.process(frame);
+        }
+        coefficients
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use nalgebra::{DMatrix, DVector};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use rustfft::num_complex::Complex64;
+
+    use super::{GaborFrame, Window};
+    use crate::matrix::MatrixComplexFields;
+
+    const N: usize = 32;
+
+    fn random_complex(len: usize, rng: &mut StdRng) -> DVector<Complex64> {
+        DVector::from_fn(len, |_, _| Complex64::new(rng.gen(), rng.gen()))
+    }
+
+    #[test]
+    fn operator_matches_synthesis_matrix() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let frame = GaborFrame::with_parameters(8, 4)
+            .with_window(Window::Hamming)
+            .with_fft_size(16);
+        let matrix = frame.synthesis_matrix(N);
+        let operator = frame.operator(N);
+        assert_eq!(matrix.shape(), (N, 8 * 16));
+
+        let coefficients = random_complex(matrix.ncols(), &mut rng);
+        let signal = DVector::from_vec(operator.synthesize(coefficients.as_slice()));
+        let expected = &matrix * &coefficients;
+        assert_relative_eq!(signal.real(), expected.real(), epsilon = 1e-12);
+        assert_relative_eq!(signal.imag(), expected.imag(), epsilon = 1e-12);
+
+        let signal = random_complex(N, &mut rng);
+        let analyzed = DVector::from_vec(operator.analyze(signal.as_slice()));
+        let expected = matrix.adjoint() * &signal;
+        assert_relative_eq!(analyzed.real(), expected.real(), epsilon = 1e-12);
+        assert_relative_eq!(analyzed.imag(), expected.imag(), epsilon = 1e-12);
+
+        for atom in matrix.column_iter() {
+            assert_relative_eq!(atom.norm(), 1.0, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "window length must be positive")]
+    fn empty_window() {
+        GaborFrame::with_parameters(0, 1);
+    }
+
+    #[test]
+    fn hann_with_quarter_hop_is_tight() {
+        let frame = GaborFrame::with_parameters(16, 4);
+        let matrix = frame.synthesis_matrix(N);
+
+        // frame operator TTᴴ proportional to the identity, bound = redundancy
+        let frame_operator = &matrix * matrix.adjoint();
+        let redundancy = matrix.ncols() as f64 / N as f64;
+        assert_relative_eq!(
+            frame_operator.real(),
+            DMatrix::identity(N, N) * redundancy,
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(frame_operator.imag(), DMatrix::zeros(N, N), epsilon = 1e-12);
+    }
+}
@@ -1,41 +1,139 @@
 extern crate derive_more;
 
-use algorithm::Algorithm;
+use algorithm::{
+    support_of, Algorithm, BinaryIterativeHardThresholdingSolver, ConsistentReconstructionSolver,
+    KalmanFilteredCsSolver, MultipleMeasurementAlgorithm, Precomputed,
+    SparseBayesianLearningSolver, TotalVariationSolver, WarmStart, Workspace, WorkspacePool,
+};
 use complex::ComplexFields;
+use entropy_coding::DecodeError;
 use matrix::Matrix;
 use measurement_matrix::MeasurementMatrix;
+use nalgebra::DMatrix;
+use nalgebra::DVectorView;
+use noise::NoiseModel;
+use one_bit::SignMeasurements;
+use precision::{Complex64, Precision};
+use quantization::{Cell, QuantizedMeasurements, Quantizer};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use sequential::{DynamicDecoder, SequentialDecoder};
+use simba::scalar::SubsetOf;
+use std::borrow::Cow;
 
 pub mod algorithm;
-mod complex;
+pub mod complex;
+pub mod dictionary_learning;
+pub mod entropy_coding;
+pub mod gabor;
 pub mod matrix;
 pub mod measurement_matrix;
+pub mod noise;
+pub mod one_bit;
+pub mod phase_transition;
+pub mod planner;
 mod precision;
+pub mod quantization;
+pub mod sequential;
 
-// TODO test crate for signal_utils
-// #[cfg(test)]
 pub mod signal_utils;
 pub mod transform_matrix;
 
 pub use transform_matrix::Transformation;
 
+// coefficients below this fraction of the largest modulus do not count towards the support,
+// so that atoms fitted to zero are not carried over from frame to frame
+const SUPPORT_THRESHOLD: f64 = 1e-3;
+
 pub struct ModelBuilder {
     algorithm: Algorithm,
+    mmv_algorithm: MultipleMeasurementAlgorithm,
     transform: Transformation,
+    measurement: MeasurementMatrix,
+    seed: Option<u64>,
+    noise: NoiseModel,
+    discrepancy_factor: f64,
+    one_bit: Option<BinaryIterativeHardThresholdingSolver>,
+    quantizer: Option<Quantizer>,
+    total_variation: Option<TotalVariationSolver>,
+    max_gram_columns: usize,
 }
 
 impl Default for ModelBuilder {
     fn default() -> Self {
         Self {
             algorithm: Default::default(),
+            mmv_algorithm: Default::default(),
             transform: Transformation::None,
+            measurement: MeasurementMatrix::Bernoulli,
+            seed: None,
+            noise: NoiseModel::None,
+            discrepancy_factor: noise::DEFAULT_DISCREPANCY_FACTOR,
+            one_bit: None,
+            quantizer: None,
+            total_variation: None,
+            max_gram_columns: algorithm::DEFAULT_MAX_GRAM_COLUMNS,
         }
     }
 }
 pub struct Model {
     algorithm: Algorithm,
+    mmv_algorithm: MultipleMeasurementAlgorithm,
     measurement_matrix: Matrix,
     transform: Matrix,
     sensing_matrix: Matrix,
+    // l2 norms of the sensing matrix columns before normalization
+    column_norms: Vec<f64>,
+    noise: NoiseModel,
+    discrepancy_factor: f64,
+    one_bit: Option<BinaryIterativeHardThresholdingSolver>,
+    quantizer: Option<Quantizer>,
+    total_variation: Option<TotalVariationSolver>,
+    // solver buffers reused by repeated decompressions, with the adjoint and Gram matrix
+    real_workspaces: WorkspacePool<f64>,
+    complex_workspaces: WorkspacePool<Complex64>,
+}
+
+/// Reconstruction with the support of its coefficients, see [`Model::decompress_warm`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseReconstruction {
+    pub signal: Vec<f64>,
+    /// indices of the significant coefficients in the transformation basis, with a modulus
+    /// above 1e-3 of the largest one
+    pub support: Vec<usize>,
+}
+
+/// Coefficients in the transformation dictionary, one per atom, see
+/// [`Model::decompress_coefficients`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Coefficients {
+    Real(Vec<f64>),
+    Complex(Vec<Complex64>),
+}
+
+impl Coefficients {
+    pub fn len(&self) -> usize {
+        match self {
+            Coefficients::Real(c) => c.len(),
+            Coefficients::Complex(c) => c.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Reconstruction with its gaussian posterior, see [`Model::decompress_with_uncertainty`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct UncertainReconstruction {
+    /// posterior mean of the signal
+    pub signal: Vec<f64>,
+    /// posterior variance of each signal sample
+    pub signal_variances: Vec<f64>,
+    /// posterior variance of each coefficient in the transformation basis
+    pub coefficient_variances: Vec<f64>,
+    /// known or estimated variance of the measurement noise
+    pub noise_variance: f64,
 }
 
 impl ModelBuilder {
@@ -48,26 +146,119 @@ impl ModelBuilder {
         self
     }
 
-    pub fn with_sensing_matrix(&mut self) -> &mut Self {
-        todo!()
+    pub fn with_measurement_matrix(&mut self, measurement: MeasurementMatrix) -> &mut Self {
+        self.measurement = measurement;
+        self
+    }
+
+    /// Seed used to generate the random measurement matrix, for reproducible models.
+    pub fn with_seed(&mut self, seed: u64) -> &mut Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Noise on the measurements, solvers then stop following the discrepancy principle.
+    pub fn with_noise(&mut self, noise: NoiseModel) -> &mut Self {
+        self.noise = noise;
+        self
+    }
+
+    /// Factor c of the discrepancy principle ‖y − Ax‖ ≤ c·σ·√m.
+    pub fn with_discrepancy_factor(&mut self, factor: f64) -> &mut Self {
+        self.discrepancy_factor = factor;
+        self
+    }
+
+    /// Enables 1-bit mode, decoding sign measurements with the given solver.
+    pub fn with_one_bit(&mut self, solver: BinaryIterativeHardThresholdingSolver) -> &mut Self {
+        self.one_bit = Some(solver);
+        self
+    }
+
+    /// Enables quantized measurements, see [`Model::compress_quantized`].
+    pub fn with_quantizer(&mut self, quantizer: Quantizer) -> &mut Self {
+        self.quantizer = Some(quantizer);
+        self
+    }
+
+    /// Enables total variation decoding, see [`Model::decompress_total_variation`].
+    pub fn with_total_variation(&mut self, solver: TotalVariationSolver) -> &mut Self {
+        self.total_variation = Some(solver);
+        self
     }
 
-    // TODO this is not accessible from the outside, as MatchingPursuitSolver is private!
     pub fn with_algorithm(&mut self, algorithm: Algorithm) -> &mut Self {
         self.algorithm = algorithm;
         self
     }
 
+    /// Largest number of signal samples for which the Gram matrix of the sensing matrix is
+    /// precomputed, speeding up matching pursuit and OMP at a memory cost quadratic in it.
+    pub fn with_max_gram_columns(&mut self, max_gram_columns: usize) -> &mut Self {
+        self.max_gram_columns = max_gram_columns;
+        self
+    }
+
+    /// Solver for jointly decompressing channels, see [`Model::decompress_channels`].
+    pub fn with_multiple_measurement_algorithm(
+        &mut self,
+        algorithm: MultipleMeasurementAlgorithm,
+    ) -> &mut Self {
+        self.mmv_algorithm = algorithm;
+        self
+    }
+
     // TODO move dimensions to new method (rename also)
     pub fn build(&self, size_compressed: usize, size_original: usize) -> Model {
-        let measurement = MeasurementMatrix::Bernoulli.into_matrix(size_compressed, size_original);
-        let transform = self.transform.into_matrix(size_original);
-        let sensing = &measurement * &transform;
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let measurement =
+            self.measurement
+                .into_matrix_with_rng(size_compressed, size_original, &mut rng);
+        let transform = self.transform.clone().into_matrix(size_original);
+        let (sensing, column_norms) = (&measurement * &transform).normalize_columns();
+        assert!(
+            !matches!(
+                (&self.algorithm, &sensing),
+                (Algorithm::ApproximateMessagePassing(_), Matrix::Complex(_))
+            ),
+            "approximate message passing needs a real transformation"
+        );
+        if let Some(solver) = &self.total_variation {
+            solver.assert_shape(size_original);
+        }
+
+        // only the greedy solvers make use of the precomputed matrices
+        let precompute = matches!(
+            self.algorithm,
+            Algorithm::MatchingPursuit(_) | Algorithm::OrthogonalMatchingPursuit(_)
+        );
+        let (real_precomputed, complex_precomputed) = match &sensing {
+            Matrix::Real(m) if precompute => {
+                (Some(Precomputed::new(m, self.max_gram_columns)), None)
+            }
+            Matrix::Complex(m) if precompute => {
+                (None, Some(Precomputed::new(m, self.max_gram_columns)))
+            }
+            _ => (None, None),
+        };
+
         Model {
-            algorithm: self.algorithm,
+            algorithm: self.algorithm.clone(),
+            mmv_algorithm: self.mmv_algorithm,
             measurement_matrix: measurement,
             transform,
             sensing_matrix: sensing,
+            column_norms,
+            noise: self.noise,
+            discrepancy_factor: self.discrepancy_factor,
+            one_bit: self.one_bit,
+            quantizer: self.quantizer,
+            total_variation: self.total_variation,
+            real_workspaces: WorkspacePool::new(real_precomputed),
+            complex_workspaces: WorkspacePool::new(complex_precomputed),
         }
     }
 }
@@ -84,20 +275,556 @@ impl Model {
         &self.measurement_matrix * orginal.as_ref()
     }
 
+    /// Compresses and adds noise according to the noise model, to simulate a real sensor.
+    pub fn compress_with_noise<T, R>(&self, orginal: T, rng: &mut R) -> Vec<f64>
+    where
+        T: AsRef<[f64]>,
+        R: Rng,
+    {
+        let mut compressed = self.compress(orginal);
+        let noise = self.noise.sample(compressed.len(), rng);
+        for (y, e) in compressed.iter_mut().zip(noise) {
+            *y += e;
+        }
+        compressed
+    }
+
     pub fn decompress<T>(&self, compressed: T) -> Vec<f64>
+    where
+        T: AsRef<[f64]>,
+    {
+        self.synthesize(&self.decompress_coefficients(compressed))
+    }
+
+    /// Number of signal samples.
+    pub fn signal_len(&self) -> usize {
+        self.transform.dimension().nrows
+    }
+
+    /// Number of coefficients, i.e. atoms of the transformation dictionary. Larger than the
+    /// signal length for overcomplete dictionaries.
+    pub fn coefficient_len(&self) -> usize {
+        self.transform.dimension().ncols
+    }
+
+    /// Recovers the sparse coefficients in the transformation dictionary, without synthesizing
+    /// the signal. They are complex for complex transformations.
+    pub fn decompress_coefficients<T>(&self, compressed: T) -> Coefficients
+    where
+        T: AsRef<[f64]>,
+    {
+        match &self.sensing_matrix {
+            Matrix::Identity(_) => Coefficients::Real(compressed.as_ref().to_vec()),
+            Matrix::Real(m) => Coefficients::Real(self.unscale(self.solve(
+                compressed.as_ref(),
+                m,
+                &self.real_workspaces,
+                None,
+            ))),
+            Matrix::Complex(m) => Coefficients::Complex(self.unscale(self.solve(
+                compressed.as_ref(),
+                m,
+                &self.complex_workspaces,
+                None,
+            ))),
+        }
+    }
+
+    /// Signal of the given coefficients, the transformation dictionary applied to them.
+    /// For complex transformations only the real part is returned.
+    pub fn synthesize(&self, coefficients: &Coefficients) -> Vec<f64> {
+        match coefficients {
+            Coefficients::Real(c) => &self.transform * c.as_slice(),
+            Coefficients::Complex(c) => (&self.transform * c.as_slice()).real(),
+        }
+    }
+
+    /// Decompresses starting from a previous, similar frame, its support or solution, see
+    /// [`Algorithm::solve_warm`] for the algorithms making use of it.
+    ///
+    /// The warm start refers to the coefficients in the transformation basis, as returned by
+    /// [`Model::decompress_coefficients`] or along with the signal. For complex transformations
+    /// a previous solution is given by real coefficients.
+    pub fn decompress_warm<T>(
+        &self,
+        compressed: T,
+        warm_start: Option<&WarmStart<f64>>,
+    ) -> SparseReconstruction
     where
         T: AsRef<[f64]>,
     {
         match &self.sensing_matrix {
-            Matrix::Identity(_) => compressed.as_ref().to_vec(),
+            Matrix::Identity(_) => SparseReconstruction {
+                signal: compressed.as_ref().to_vec(),
+                support: support_of(compressed.as_ref(), SUPPORT_THRESHOLD),
+            },
             Matrix::Real(m) => {
-                let sparse = self.algorithm.solve(&compressed, &m);
+                let warm_start = warm_start.map(|w| self.scale(w));
+                let sparse = self.unscale(self.solve(
+                    compressed.as_ref(),
+                    m,
+                    &self.real_workspaces,
+                    warm_start.as_ref(),
+                ));
+                SparseReconstruction {
+                    signal: &self.transform * sparse.as_slice(),
+                    support: support_of(&sparse, SUPPORT_THRESHOLD),
+                }
+            }
+            Matrix::Complex(m) => {
+                let warm_start = warm_start.map(|w| self.scale(w));
+                let sparse = self.unscale(self.solve(
+                    compressed.as_ref(),
+                    m,
+                    &self.complex_workspaces,
+                    warm_start.as_ref(),
+                ));
+                SparseReconstruction {
+                    signal: (&self.transform * sparse.as_slice()).real(),
+                    support: support_of(&sparse, SUPPORT_THRESHOLD),
+                }
+            }
+        }
+    }
+
+    /// Decoder for a sequence of frames, see [`SequentialDecoder`].
+    pub fn sequential_decoder(&self) -> SequentialDecoder<'_> {
+        SequentialDecoder::new(self)
+    }
+
+    /// Dynamic compressed sensing decoder for a sequence of frames, see [`DynamicDecoder`].
+    pub fn dynamic_decoder(&self, solver: KalmanFilteredCsSolver) -> DynamicDecoder<'_> {
+        DynamicDecoder::new(self, solver)
+    }
+
+    /// Decompresses independent frames in parallel, the results are in the order of `frames`.
+    ///
+    /// Each worker thread reuses one solver [`Workspace`] for all of its frames, taken from and
+    /// given back to the workspaces of the model.
+    #[cfg(feature = "rayon")]
+    pub fn decompress_batch<T>(&self, frames: &[T]) -> Vec<Vec<f64>>
+    where
+        T: AsRef<[f64]> + Sync,
+    {
+        use rayon::prelude::*;
+
+        match &self.sensing_matrix {
+            Matrix::Identity(_) => frames.iter().map(|f| f.as_ref().to_vec()).collect(),
+            Matrix::Real(m) => frames
+                .par_iter()
+                .map_init(
+                    || self.real_workspaces.take(),
+                    |workspace, frame| {
+                        let sparse = self.unscale(self.solve_with_workspace(
+                            frame.as_ref(),
+                            m,
+                            workspace,
+                            None,
+                        ));
+                        &self.transform * sparse.as_slice()
+                    },
+                )
+                .collect(),
+            Matrix::Complex(m) => frames
+                .par_iter()
+                .map_init(
+                    || self.complex_workspaces.take(),
+                    |workspace, frame| {
+                        let sparse = self.unscale(self.solve_with_workspace(
+                            frame.as_ref(),
+                            m,
+                            workspace,
+                            None,
+                        ));
+                        (&self.transform * sparse.as_slice()).real()
+                    },
+                )
+                .collect(),
+        }
+    }
+
+    /// Compresses several channels with the same measurement matrix.
+    pub fn compress_channels<T>(&self, channels: &[T]) -> Vec<Vec<f64>>
+    where
+        T: AsRef<[f64]>,
+    {
+        channels.iter().map(|c| self.compress(c)).collect()
+    }
+
+    /// Jointly decompresses channels sharing the same sparse support
+    /// (multiple measurement vectors). Each channel must have as many measurements as the model.
+    pub fn decompress_channels<T>(&self, compressed: &[T]) -> Vec<Vec<f64>>
+    where
+        T: AsRef<[f64]>,
+    {
+        if compressed.is_empty() {
+            return Vec::new();
+        }
+        let nrows = self.sensing_matrix.dimension().nrows;
+        for (j, channel) in compressed.iter().enumerate() {
+            assert_eq!(
+                channel.as_ref().len(),
+                nrows,
+                "channel {j} has {} measurements, the model expects {nrows}",
+                channel.as_ref().len()
+            );
+        }
+        let y = DMatrix::from_fn(nrows, compressed.len(), |i, j| compressed[j].as_ref()[i]);
+
+        match &self.sensing_matrix {
+            Matrix::Identity(_) => compressed.iter().map(|c| c.as_ref().to_vec()).collect(),
+            Matrix::Real(m) => self
+                .mmv_algorithm
+                .solve(&y, m)
+                .column_iter()
+                .map(|sparse| {
+                    let sparse = self.unscale(sparse.iter().copied().collect());
+                    &self.transform * sparse.as_slice()
+                })
+                .collect(),
+            Matrix::Complex(m) => self
+                .mmv_algorithm
+                .solve(&y, m)
+                .column_iter()
+                .map(|sparse| {
+                    let sparse = self.unscale(sparse.iter().copied().collect());
+                    (&self.transform * sparse.as_slice()).real()
+                })
+                .collect(),
+        }
+    }
+
+    /// Compresses to the signs of the measurements, with the norm of the signal as side information.
+    pub fn compress_one_bit<T>(&self, orginal: T) -> SignMeasurements
+    where
+        T: AsRef<[f64]>,
+    {
+        let norm = orginal.as_ref().iter().map(|e| e * e).sum::<f64>().sqrt();
+        SignMeasurements::from_measurements(&self.compress(orginal), Some(norm))
+    }
+
+    /// Recovers the signal from sign measurements. Without norm side information
+    /// the result has unit l2 norm.
+    pub fn decompress_one_bit(&self, compressed: &SignMeasurements) -> Vec<f64> {
+        let solver = self
+            .one_bit
+            .expect("1-bit mode is not enabled, see ModelBuilder::with_one_bit");
+        let signs = compressed.signs();
+        let signs = DVectorView::from_slice(&signs, signs.len());
+
+        let mut decompressed = match &self.sensing_matrix {
+            Matrix::Identity(_) => signs.iter().copied().collect(),
+            Matrix::Real(m) => {
+                let sparse = self.unscale(solver.solve(&signs, m).data.into());
                 &self.transform * sparse.as_slice()
             }
             Matrix::Complex(m) => {
-                let sparse = self.algorithm.solve(&compressed, &m);
+                let sparse = self.unscale(solver.solve(&signs, m).data.into());
                 (&self.transform * sparse.as_slice()).real()
             }
+        };
+
+        let norm = decompressed.iter().map(|e| e * e).sum::<f64>().sqrt();
+        let scale = compressed.norm().unwrap_or(1.0) / norm;
+        if norm > 0.0 {
+            decompressed.iter_mut().for_each(|e| *e *= scale);
+        }
+        decompressed
+    }
+
+    /// Compresses and quantizes the measurements to a bit packed representation.
+    pub fn compress_quantized<T>(&self, orginal: T) -> QuantizedMeasurements
+    where
+        T: AsRef<[f64]>,
+    {
+        self.quantizer
+            .expect("quantization is not enabled, see ModelBuilder::with_quantizer")
+            .quantize(&self.compress(orginal))
+    }
+
+    /// Decompresses quantized measurements. The quantization error is treated as bounded noise:
+    /// the solver stops at the discrepancy of the quantization noise and the solution is refined
+    /// to be consistent with the quantization cells.
+    ///
+    /// The measurements must come from the quantizer of the model, with one per row of the
+    /// measurement matrix.
+    pub fn decompress_quantized(&self, compressed: &QuantizedMeasurements) -> Vec<f64> {
+        let quantizer = self
+            .quantizer
+            .expect("quantization is not enabled, see ModelBuilder::with_quantizer");
+        let rows = self.measurement_matrix.dimension().nrows;
+        assert_eq!(
+            compressed.len(),
+            rows,
+            "{} quantized measurements, the model expects {rows}",
+            compressed.len()
+        );
+        assert_eq!(
+            compressed.bits(),
+            quantizer.bits(),
+            "measurements quantized with {} bits, the model quantizes with {}",
+            compressed.bits(),
+            quantizer.bits()
+        );
+        let dequantized = quantizer.dequantize(compressed);
+        let cells = quantizer.cells(compressed);
+        let tolerance = noise::discrepancy(
+            quantizer.noise_sigma(compressed),
+            compressed.len(),
+            self.discrepancy_factor,
+        );
+
+        match &self.sensing_matrix {
+            Matrix::Identity(_) => dequantized,
+            Matrix::Real(m) => {
+                let sparse = self.unscale(self.solve_quantized(&dequantized, &cells, tolerance, m));
+                &self.transform * sparse.as_slice()
+            }
+            Matrix::Complex(m) => {
+                let sparse = self.unscale(self.solve_quantized(&dequantized, &cells, tolerance, m));
+                (&self.transform * sparse.as_slice()).real()
+            }
+        }
+    }
+
+    /// Compresses, quantizes and entropy codes the signal into a self-contained byte stream.
+    pub fn compress_encoded<T>(&self, orginal: T) -> Vec<u8>
+    where
+        T: AsRef<[f64]>,
+    {
+        let quantizer = self
+            .quantizer
+            .expect("quantization is not enabled, see ModelBuilder::with_quantizer");
+        entropy_coding::encode(&quantizer, &self.compress_quantized(orginal))
+    }
+
+    /// Inverse of [`Model::compress_encoded`]. Streams with more measurements than the model
+    /// has or quantized differently than by its quantizer are rejected.
+    pub fn decompress_encoded(&self, encoded: &[u8]) -> Result<Vec<f64>, DecodeError> {
+        let expected = self
+            .quantizer
+            .expect("quantization is not enabled, see ModelBuilder::with_quantizer");
+        let rows = self.measurement_matrix.dimension().nrows;
+        let (quantizer, quantized) = entropy_coding::decode(encoded, rows)?;
+        if quantized.len() != rows {
+            return Err(DecodeError::MeasurementCount(quantized.len(), rows));
+        }
+        if !quantizer.is_compatible(&expected) {
+            return Err(DecodeError::QuantizerMismatch);
+        }
+        Ok(self.decompress_quantized(&quantized))
+    }
+
+    /// Recovers a signal with sparse gradient by total variation minimization on the
+    /// measurement matrix, i.e. independent of the transformation.
+    pub fn decompress_total_variation<T>(&self, compressed: T) -> Vec<f64>
+    where
+        T: AsRef<[f64]>,
+    {
+        let solver = self
+            .total_variation
+            .expect("total variation is not enabled, see ModelBuilder::with_total_variation");
+        let compressed = compressed.as_ref();
+
+        match &self.measurement_matrix {
+            Matrix::Identity(_) => compressed.to_vec(),
+            Matrix::Real(m) => {
+                let y = DVectorView::from_slice(compressed, compressed.len());
+                let solver = match self.discrepancy(compressed, m) {
+                    Some(tolerance) => solver.with_tolerance(tolerance),
+                    None => solver,
+                };
+                solver.solve(&y, m).data.into()
+            }
+            Matrix::Complex(_) => unreachable!("measurement matrices are real"),
+        }
+    }
+
+    /// Decompresses with sparse Bayesian learning, returning error bars along with the signal.
+    ///
+    /// Requires [`Algorithm::SparseBayesianLearning`]. A known gaussian noise model fixes the
+    /// noise variance, otherwise it is estimated. For complex transformations the variances are
+    /// those of the complex samples.
+    pub fn decompress_with_uncertainty<T>(&self, compressed: T) -> UncertainReconstruction
+    where
+        T: AsRef<[f64]>,
+    {
+        let Algorithm::SparseBayesianLearning(solver) = &self.algorithm else {
+            panic!("uncertainty needs Algorithm::SparseBayesianLearning, see ModelBuilder::with_algorithm");
+        };
+        let solver = match self.noise {
+            NoiseModel::Gaussian { sigma } => solver.with_noise_variance(sigma * sigma),
+            NoiseModel::None | NoiseModel::Estimated => *solver,
+        };
+        let compressed = compressed.as_ref();
+
+        match (&self.sensing_matrix, &self.transform) {
+            (Matrix::Identity(_), _) => UncertainReconstruction {
+                signal: compressed.to_vec(),
+                signal_variances: vec![0.0; compressed.len()],
+                coefficient_variances: vec![0.0; compressed.len()],
+                noise_variance: 0.0,
+            },
+            (Matrix::Real(m), Matrix::Identity(_)) => self.posterior(solver, compressed, m, None),
+            (Matrix::Real(m), Matrix::Real(t)) => self.posterior(solver, compressed, m, Some(t)),
+            (Matrix::Complex(m), Matrix::Identity(_)) => {
+                self.posterior(solver, compressed, m, None)
+            }
+            (Matrix::Complex(m), Matrix::Complex(t)) => {
+                self.posterior(solver, compressed, m, Some(t))
+            }
+            (Matrix::Real(_), Matrix::Complex(_)) | (Matrix::Complex(_), Matrix::Real(_)) => {
+                unreachable!("the sensing matrix has the precision of the transformation")
+            }
+        }
+    }
+
+    fn posterior<P>(
+        &self,
+        solver: SparseBayesianLearningSolver,
+        compressed: &[f64],
+        matrix: &DMatrix<P>,
+        transform: Option<&DMatrix<P>>,
+    ) -> UncertainReconstruction
+    where
+        P: Precision,
+        P::RealField: SubsetOf<f64>,
+    {
+        let y = DVectorView::from_slice(compressed, compressed.len());
+        let mut estimate = solver.solve(&y, matrix);
+
+        // undo the column normalization on mean and covariance
+        let norms = nalgebra::DVector::from_column_slice(&self.column_norms);
+        for (mean, norm) in estimate.mean.iter_mut().zip(norms.iter()) {
+            *mean /= nalgebra::convert::<f64, P>(*norm);
+        }
+        let scaling = &norms * norms.transpose();
+        estimate
+            .covariance
+            .zip_apply(&scaling, |covariance, scale| {
+                *covariance /= nalgebra::convert::<f64, P>(scale)
+            });
+        let coefficient_variances = estimate.variances();
+
+        // diag(TΣTᴴ) for the signal samples x = Tc
+        let (signal, signal_variances) = match transform {
+            Some(t) => {
+                let variances = (t * &estimate.covariance)
+                    .component_mul(&t.conjugate())
+                    .column_sum()
+                    .map(|e| nalgebra::convert::<_, f64>(e.real()).max(0.0));
+                (t * &estimate.mean, variances)
+            }
+            None => (estimate.mean, coefficient_variances.clone()),
+        };
+
+        UncertainReconstruction {
+            signal: signal.iter().map(|e| nalgebra::convert(e.real())).collect(),
+            signal_variances: signal_variances.data.into(),
+            coefficient_variances: coefficient_variances.data.into(),
+            noise_variance: estimate.noise_variance,
+        }
+    }
+
+    fn solve_quantized<P>(
+        &self,
+        dequantized: &[f64],
+        cells: &[Cell],
+        tolerance: f64,
+        matrix: &DMatrix<P>,
+    ) -> Vec<P>
+    where
+        P: Precision,
+        P::RealField: SubsetOf<f64>,
+    {
+        let initial = self
+            .algorithm
+            .with_tolerance(tolerance)
+            .solve(&dequantized, matrix);
+        ConsistentReconstructionSolver::with_parameters(100)
+            .solve(nalgebra::DVector::from_vec(initial), cells, matrix)
+            .data
+            .into()
+    }
+
+    fn solve<P>(
+        &self,
+        compressed: &[f64],
+        matrix: &DMatrix<P>,
+        pool: &WorkspacePool<P>,
+        warm_start: Option<&WarmStart<P>>,
+    ) -> Vec<P>
+    where
+        P: Precision,
+        P::RealField: SubsetOf<f64>,
+    {
+        self.solve_with_workspace(compressed, matrix, &mut pool.take(), warm_start)
+    }
+
+    fn solve_with_workspace<P>(
+        &self,
+        compressed: &[f64],
+        matrix: &DMatrix<P>,
+        workspace: &mut Workspace<P>,
+        warm_start: Option<&WarmStart<P>>,
+    ) -> Vec<P>
+    where
+        P: Precision,
+        P::RealField: SubsetOf<f64>,
+    {
+        let algorithm = match self.discrepancy(compressed, matrix) {
+            Some(tolerance) => Cow::Owned(self.algorithm.with_tolerance(tolerance)),
+            None => Cow::Borrowed(&self.algorithm),
+        };
+        match warm_start {
+            Some(warm_start) => algorithm.solve_warm(&compressed, matrix, workspace, warm_start),
+            None => algorithm.solve_with_workspace(&compressed, matrix, workspace),
+        }
+    }
+
+    // residual norm allowed by the noise model, none for perfect measurements
+    fn discrepancy<P>(&self, compressed: &[f64], matrix: &DMatrix<P>) -> Option<f64>
+    where
+        P: Precision,
+        P::RealField: SubsetOf<f64>,
+    {
+        let sigma = match self.noise {
+            NoiseModel::None => return None,
+            NoiseModel::Gaussian { sigma } => sigma,
+            NoiseModel::Estimated => noise::estimate_noise(compressed, matrix),
+        };
+        Some(noise::discrepancy(
+            sigma,
+            matrix.nrows(),
+            self.discrepancy_factor,
+        ))
+    }
+
+    // warm start for the normalized sensing matrix, inverse of unscale
+    fn scale<P>(&self, warm_start: &WarmStart<f64>) -> WarmStart<P>
+    where
+        P: Precision,
+    {
+        match warm_start {
+            WarmStart::Support(support) => WarmStart::Support(support.clone()),
+            WarmStart::Solution(solution) => WarmStart::Solution(nalgebra::DVector::from_vec(
+                solution
+                    .iter()
+                    .zip(self.column_norms.iter())
+                    .map(|(coefficient, norm)| nalgebra::convert(coefficient * norm))
+                    .collect(),
+            )),
+        }
+    }
+
+    // undo the column normalization of the sensing matrix on the solved coefficients
+    fn unscale<P>(&self, mut sparse: Vec<P>) -> Vec<P>
+    where
+        P: std::ops::DivAssign<f64>,
+    {
+        for (coefficient, norm) in sparse.iter_mut().zip(self.column_norms.iter()) {
+            *coefficient /= *norm;
         }
+        sparse
     }
 }
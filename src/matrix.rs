@@ -1,14 +1,8 @@
-use std::{
-    fmt::Display,
-    ops::{Add, Mul},
-};
+use std::{fmt::Display, ops::Mul};
 
 use derive_more::{Display, From};
-use nalgebra::{
-    constraint::AreMultipliable, ClosedMul, Complex, ComplexField, DMatrix, DVectorView, Dyn,
-    RealField, VectorView, U1,
-};
-use simba::scalar::{SubsetOf, SupersetOf};
+use nalgebra::{DMatrix, DVectorView};
+use simba::scalar::SubsetOf;
 
 use crate::precision::{Complex64, Precision};
 
@@ -49,8 +43,7 @@ impl Dimension {
 
 impl Display for Dimension {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("(r: {}, c: {})", self.nrows, self.ncols));
-        Ok(())
+        f.write_fmt(format_args!("(r: {}, c: {})", self.nrows, self.ncols))
     }
 }
 
@@ -61,6 +54,58 @@ pub enum Matrix {
     Complex(ComplexMatrix),
 }
 
+impl Matrix {
+    pub fn dimension(&self) -> Dimension {
+        match self {
+            Matrix::Identity(dim) => *dim,
+            Matrix::Real(matrix) => Dimension {
+                nrows: matrix.nrows(),
+                ncols: matrix.ncols(),
+            },
+            Matrix::Complex(matrix) => Dimension {
+                nrows: matrix.nrows(),
+                ncols: matrix.ncols(),
+            },
+        }
+    }
+
+    /// Scales each column to unit l2 norm.
+    /// Returns the normalized matrix and the original column norms, so the scaling can be undone.
+    /// Columns with zero norm are left untouched and reported with a norm of 1.
+    pub fn normalize_columns(&self) -> (Matrix, Vec<f64>) {
+        match self {
+            Matrix::Identity(dim) => (self.clone(), vec![1.0; dim.ncols]),
+            Matrix::Real(matrix) => {
+                let (normalized, norms) = normalize_columns(matrix);
+                (normalized.into(), norms)
+            }
+            Matrix::Complex(matrix) => {
+                let (normalized, norms) = normalize_columns(matrix);
+                (normalized.into(), norms)
+            }
+        }
+    }
+}
+
+fn normalize_columns<P>(matrix: &DMatrix<P>) -> (DMatrix<P>, Vec<f64>)
+where
+    P: Precision,
+    P::RealField: SubsetOf<f64>,
+{
+    let mut normalized = matrix.clone();
+    let mut norms = Vec::with_capacity(matrix.ncols());
+    for mut col in normalized.column_iter_mut() {
+        let norm: f64 = nalgebra::convert(col.norm());
+        if norm > 0.0 {
+            col.unscale_mut(nalgebra::convert(norm));
+            norms.push(norm);
+        } else {
+            norms.push(1.0);
+        }
+    }
+    (normalized, norms)
+}
+
 impl Mul<Matrix> for Matrix {
     type Output = Matrix;
 
@@ -190,3 +235,44 @@ where
 //         todo!()
 //     }
 // }
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use nalgebra::dmatrix;
+
+    use super::{Dimension, Matrix, RealMatrix};
+
+    #[test]
+    fn normalize_columns_to_unit_norm() {
+        let matrix: Matrix = dmatrix![
+            3.0, 0.0, 0.0;
+            4.0, 2.0, 0.0;
+        ]
+        .into();
+
+        let (normalized, norms) = matrix.normalize_columns();
+
+        assert_eq!(norms, vec![5.0, 2.0, 1.0]);
+        let normalized: RealMatrix = match normalized {
+            Matrix::Real(m) => m,
+            _ => panic!("expected a real matrix"),
+        };
+        assert_relative_eq!(
+            normalized,
+            dmatrix![
+                0.6, 0.0, 0.0;
+                0.8, 1.0, 0.0;
+            ]
+        );
+    }
+
+    #[test]
+    fn normalize_identity_is_noop() {
+        let dim = Dimension { nrows: 3, ncols: 3 };
+        let (normalized, norms) = Matrix::Identity(dim).normalize_columns();
+
+        assert!(matches!(normalized, Matrix::Identity(d) if d == dim));
+        assert_eq!(norms, vec![1.0; 3]);
+    }
+}
@@ -1,41 +1,79 @@
 use nalgebra::DMatrix;
-use rand::{distributions::Bernoulli, prelude::Distribution};
+use rand::{distributions::Bernoulli, prelude::Distribution, Rng};
+use rand_distr::Normal;
 
 use crate::matrix::{Matrix, RealMatrix};
 
+#[derive(Clone, Copy, Debug)]
 pub enum MeasurementMatrix {
     Bernoulli,
+    Gaussian,
 }
 
 impl MeasurementMatrix {
     pub fn into_matrix(self, nrows: usize, ncolumns: usize) -> Matrix {
+        self.into_matrix_with_rng(nrows, ncolumns, &mut rand::thread_rng())
+    }
+
+    pub fn into_matrix_with_rng<R>(self, nrows: usize, ncolumns: usize, rng: &mut R) -> Matrix
+    where
+        R: Rng,
+    {
         match self {
             MeasurementMatrix::Bernoulli => {
-                Matrix::Real(MeasurementMatrix::bernoulli(nrows, ncolumns))
+                Matrix::Real(MeasurementMatrix::bernoulli(nrows, ncolumns, rng))
+            }
+            MeasurementMatrix::Gaussian => {
+                Matrix::Real(MeasurementMatrix::gaussian(nrows, ncolumns, rng))
             }
         }
     }
 
-    fn bernoulli(nrows: usize, ncolumns: usize) -> RealMatrix {
-        // TODO make this injectable
-        let rng = rand::thread_rng();
+    fn bernoulli<R>(nrows: usize, ncolumns: usize, rng: &mut R) -> RealMatrix
+    where
+        R: Rng,
+    {
         let norm = 1.0 / ((ncolumns as f64).sqrt());
         let mut dist =
             Bernoulli::new(0.5)
                 .unwrap()
                 .sample_iter(rng)
                 .map(|v| if v { norm } else { -norm });
-        DMatrix::from_fn(nrows, ncolumns, |_, _| dist.next().unwrap()).into()
+        DMatrix::from_fn(nrows, ncolumns, |_, _| dist.next().unwrap())
+    }
+
+    fn gaussian<R>(nrows: usize, ncolumns: usize, rng: &mut R) -> RealMatrix
+    where
+        R: Rng,
+    {
+        let std = 1.0 / ((ncolumns as f64).sqrt());
+        let dist = Normal::new(0.0, std).unwrap();
+        DMatrix::from_fn(nrows, ncolumns, |_, _| dist.sample(rng))
     }
 }
 
 #[cfg(test)]
 mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
     use super::MeasurementMatrix;
 
     #[test]
     fn bernoulli() {
-        let s = MeasurementMatrix::bernoulli(5, 10);
+        let s = MeasurementMatrix::bernoulli(5, 10, &mut rand::thread_rng());
         println!("Generated bernoulli matrix {:?}", s)
     }
+
+    #[test]
+    fn gaussian() {
+        let s = MeasurementMatrix::gaussian(5, 10, &mut rand::thread_rng());
+        println!("Generated gaussian matrix {:?}", s)
+    }
+
+    #[test]
+    fn bernoulli_is_reproducible_with_seed() {
+        let a = MeasurementMatrix::bernoulli(5, 10, &mut StdRng::seed_from_u64(42));
+        let b = MeasurementMatrix::bernoulli(5, 10, &mut StdRng::seed_from_u64(42));
+        assert_eq!(a, b);
+    }
 }
@@ -0,0 +1,111 @@
+use nalgebra::{DMatrix, DVectorView};
+use rand::Rng;
+use rand_distr::StandardNormal;
+use simba::scalar::SubsetOf;
+
+use crate::{algorithm::OrthogonalMatchingPursuitSolver, precision::Precision};
+
+/// Noise on the measurements, i.e. y = Ax + e.
+///
+/// If the noise is known or estimated, solvers stop following the discrepancy principle
+/// ‖y − Ax‖ ≤ c·σ·√m instead of using their fixed tolerance.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum NoiseModel {
+    /// perfect measurements, solvers use their configured tolerance
+    #[default]
+    None,
+    /// additive white gaussian noise with known standard deviation
+    Gaussian { sigma: f64 },
+    /// additive white gaussian noise, the standard deviation is estimated from the residual of
+    /// an initial reconstruction
+    Estimated,
+}
+
+pub const DEFAULT_DISCREPANCY_FACTOR: f64 = 1.1;
+
+impl NoiseModel {
+    /// Samples `len` noise values, all zero unless the noise model is gaussian with known sigma.
+    pub fn sample<R>(&self, len: usize, rng: &mut R) -> Vec<f64>
+    where
+        R: Rng,
+    {
+        match self {
+            NoiseModel::Gaussian { sigma } => (0..len)
+                .map(|_| sigma * rng.sample::<f64, _>(StandardNormal))
+                .collect(),
+            NoiseModel::None | NoiseModel::Estimated => vec![0.0; len],
+        }
+    }
+}
+
+/// Residual norm c·σ·√m up to which a reconstruction is consistent with the noise.
+pub fn discrepancy(sigma: f64, nmeasurements: usize, factor: f64) -> f64 {
+    factor * sigma * (nmeasurements as f64).sqrt()
+}
+
+/// Unbiased estimate of the noise standard deviation from the residual of a least squares fit
+/// using `support` coefficients.
+pub fn estimate_sigma(residual_norm: f64, nmeasurements: usize, support: usize) -> f64 {
+    if support >= nmeasurements {
+        return 0.0;
+    }
+    residual_norm / ((nmeasurements - support) as f64).sqrt()
+}
+
+/// Estimates the noise standard deviation of the measurements `y` of the sensing matrix.
+///
+/// The signal is approximated with m/4 atoms by OMP, assuming the actual sparsity is below,
+/// so that the remaining residual is dominated by noise.
+pub fn estimate_noise<P>(y: &[f64], sensing_matrix: &DMatrix<P>) -> f64
+where
+    P: Precision,
+    P::RealField: SubsetOf<f64>,
+{
+    let nmeasurements = sensing_matrix.nrows();
+    let support = nmeasurements / 4;
+    let omp = OrthogonalMatchingPursuitSolver::with_parameters(support, 0.0);
+
+    let y = DVectorView::from_slice(y, nmeasurements);
+    let fitted = sensing_matrix * omp.solve(&y, sensing_matrix);
+    let residual_norm: f64 = y
+        .iter()
+        .zip(fitted.iter())
+        .map(|(y, f)| nalgebra::convert::<_, f64>((P::from_subset(y) - *f).modulus_squared()))
+        .sum::<f64>()
+        .sqrt();
+
+    estimate_sigma(residual_norm, nmeasurements, support)
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{discrepancy, estimate_sigma, NoiseModel};
+
+    #[test]
+    fn discrepancy_scales_with_measurements() {
+        assert_relative_eq!(discrepancy(0.5, 16, 1.0), 2.0);
+        assert_relative_eq!(discrepancy(0.5, 16, 1.5), 3.0);
+    }
+
+    #[test]
+    fn estimate_accounts_for_degrees_of_freedom() {
+        assert_relative_eq!(estimate_sigma(3.0, 13, 4), 1.0);
+        assert_eq!(estimate_sigma(3.0, 4, 4), 0.0);
+    }
+
+    #[test]
+    fn gaussian_samples_have_sigma() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let samples = NoiseModel::Gaussian { sigma: 0.1 }.sample(10000, &mut rng);
+
+        let std = (samples.iter().map(|e| e * e).sum::<f64>() / samples.len() as f64).sqrt();
+        assert_relative_eq!(std, 0.1, epsilon = 0.005);
+        assert!(NoiseModel::None
+            .sample(4, &mut rng)
+            .iter()
+            .all(|e| *e == 0.0));
+    }
+}
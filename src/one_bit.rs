@@ -0,0 +1,95 @@
+/// Signs of the measurements packed into bits, 1 for positive (or zero) and 0 for negative.
+///
+/// The l2 norm of the original signal can be attached as side information, as it is lost
+/// by the 1-bit quantization.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignMeasurements {
+    bits: Vec<u8>,
+    len: usize,
+    norm: Option<f64>,
+}
+
+impl SignMeasurements {
+    pub fn from_measurements(measurements: &[f64], norm: Option<f64>) -> Self {
+        let mut bits = vec![0u8; measurements.len().div_ceil(8)];
+        for (i, _) in measurements.iter().enumerate().filter(|(_, m)| **m >= 0.0) {
+            bits[i / 8] |= 1 << (i % 8);
+        }
+        Self {
+            bits,
+            len: measurements.len(),
+            norm,
+        }
+    }
+
+    pub fn from_bytes(bits: Vec<u8>, len: usize, norm: Option<f64>) -> Self {
+        assert!(
+            len.div_ceil(8) <= bits.len(),
+            "{} bytes can't hold {len} signs",
+            bits.len()
+        );
+        Self { bits, len, norm }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bits
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn norm(&self) -> Option<f64> {
+        self.norm
+    }
+
+    pub fn without_norm(self) -> Self {
+        Self { norm: None, ..self }
+    }
+
+    /// Unpacks the bits into +1/-1 values.
+    pub fn signs(&self) -> Vec<f64> {
+        (0..self.len)
+            .map(|i| {
+                if self.bits[i / 8] & (1 << (i % 8)) != 0 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SignMeasurements;
+
+    #[test]
+    fn packs_signs_into_bits() {
+        let measurements = [0.5, -1.0, 0.0, -0.1, 2.0, 3.0, -4.0, 1.0, -0.3];
+        let packed = SignMeasurements::from_measurements(&measurements, Some(2.0));
+
+        assert_eq!(packed.as_bytes(), &[0b1011_0101, 0b0000_0000]);
+        assert_eq!(packed.len(), 9);
+        assert_eq!(
+            packed.signs(),
+            vec![1.0, -1.0, 1.0, -1.0, 1.0, 1.0, -1.0, 1.0, -1.0]
+        );
+        assert_eq!(packed.norm(), Some(2.0));
+        assert_eq!(packed.clone().without_norm().norm(), None);
+
+        let unpacked = SignMeasurements::from_bytes(packed.as_bytes().to_vec(), 9, Some(2.0));
+        assert_eq!(unpacked, packed);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't hold")]
+    fn rejects_too_few_bytes() {
+        SignMeasurements::from_bytes(vec![0xff], 9, None);
+    }
+}
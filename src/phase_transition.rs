@@ -0,0 +1,206 @@
+use std::io::Write;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    algorithm::{Algorithm, OrthogonalMatchingPursuitSolver},
+    measurement_matrix::MeasurementMatrix,
+    signal_utils::{generate_sparse_signal_with_rng, relative_error_l2},
+    ModelBuilder, Transformation,
+};
+
+/// Empirical Donoho–Tanner phase transition.
+///
+/// Sweeps the undersampling ratio δ = m/n and the sparsity ratio ρ = k/m over a grid
+/// and estimates the probability of a successful reconstruction in each cell.
+pub struct PhaseTransition {
+    len: usize,
+    deltas: Vec<f64>,
+    rhos: Vec<f64>,
+    trials: usize,
+    seed: u64,
+    tolerance: f64,
+    algorithm: Algorithm,
+    measurement: MeasurementMatrix,
+}
+
+/// Success probabilities of a phase transition sweep, indexed as `success[rho][delta]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseDiagram {
+    pub deltas: Vec<f64>,
+    pub rhos: Vec<f64>,
+    pub success: Vec<Vec<f64>>,
+}
+
+impl PhaseTransition {
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            deltas: linspace(0.1, 1.0, 10),
+            rhos: linspace(0.1, 1.0, 10),
+            trials: 20,
+            seed: 0,
+            tolerance: 1e-2,
+            algorithm: Algorithm::OrthogonalMatchingPursuit(
+                OrthogonalMatchingPursuitSolver::with_parameters(len, 1e-6),
+            ),
+            measurement: MeasurementMatrix::Bernoulli,
+        }
+    }
+
+    pub fn with_grid(&mut self, deltas: Vec<f64>, rhos: Vec<f64>) -> &mut Self {
+        self.deltas = deltas;
+        self.rhos = rhos;
+        self
+    }
+
+    pub fn with_trials(&mut self, trials: usize) -> &mut Self {
+        self.trials = trials;
+        self
+    }
+
+    pub fn with_seed(&mut self, seed: u64) -> &mut Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Relative l2 error below which a reconstruction counts as successful.
+    pub fn with_success_tolerance(&mut self, tolerance: f64) -> &mut Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_algorithm(&mut self, algorithm: Algorithm) -> &mut Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    pub fn with_measurement_matrix(&mut self, measurement: MeasurementMatrix) -> &mut Self {
+        self.measurement = measurement;
+        self
+    }
+
+    pub fn run(&self) -> PhaseDiagram {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let success = self
+            .rhos
+            .iter()
+            .map(|&rho| {
+                self.deltas
+                    .iter()
+                    .map(|&delta| self.success_rate(delta, rho, &mut rng))
+                    .collect()
+            })
+            .collect();
+
+        PhaseDiagram {
+            deltas: self.deltas.clone(),
+            rhos: self.rhos.clone(),
+            success,
+        }
+    }
+
+    fn success_rate(&self, delta: f64, rho: f64, rng: &mut StdRng) -> f64 {
+        let size_compressed = ((delta * self.len as f64).round() as usize).clamp(1, self.len);
+        let sparsity = ((rho * size_compressed as f64).round() as usize).clamp(1, self.len);
+
+        let successes = (0..self.trials)
+            .filter(|_| {
+                let model = ModelBuilder::new()
+                    .with_transformation(Transformation::None)
+                    .with_measurement_matrix(self.measurement)
+                    .with_algorithm(self.algorithm.clone())
+                    .with_seed(rng.gen())
+                    .build(size_compressed, self.len);
+
+                let original = generate_sparse_signal_with_rng(self.len, sparsity, rng);
+                let decompressed = model.decompress(model.compress(&original));
+
+                relative_error_l2(&original, &decompressed) < self.tolerance
+            })
+            .count();
+
+        successes as f64 / self.trials as f64
+    }
+}
+
+impl PhaseDiagram {
+    /// Writes the grid as CSV with one `delta,rho,success` row per cell.
+    pub fn write_csv<W>(&self, mut writer: W) -> std::io::Result<()>
+    where
+        W: Write,
+    {
+        writeln!(writer, "delta,rho,success")?;
+        for (rho, row) in self.rhos.iter().zip(self.success.iter()) {
+            for (delta, success) in self.deltas.iter().zip(row.iter()) {
+                writeln!(writer, "{},{},{}", delta, rho, success)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn linspace(start: f64, end: f64, steps: usize) -> Vec<f64> {
+    match steps {
+        0 => vec![],
+        1 => vec![start],
+        _ => {
+            let step = (end - start) / (steps - 1) as f64;
+            (0..steps).map(|i| start + step * i as f64).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{linspace, PhaseTransition};
+
+    #[test]
+    fn linspace_includes_both_ends() {
+        assert_eq!(linspace(0.0, 1.0, 5), vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn easy_cells_succeed_and_hard_cells_fail() {
+        let diagram = PhaseTransition::new(32)
+            .with_grid(vec![0.1, 1.0], vec![0.1, 1.0])
+            .with_trials(5)
+            .with_seed(42)
+            .run();
+
+        // few measurements, all of them "sparse" coefficients
+        assert_eq!(diagram.success[1][0], 0.0);
+        // full measurements of a very sparse signal
+        assert_eq!(diagram.success[0][1], 1.0);
+    }
+
+    #[test]
+    fn is_reproducible_with_seed() {
+        let run = || {
+            PhaseTransition::new(16)
+                .with_grid(vec![0.5], vec![0.2, 0.4])
+                .with_trials(4)
+                .with_seed(7)
+                .run()
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn writes_csv() {
+        let diagram = PhaseTransition::new(8)
+            .with_grid(vec![0.5, 1.0], vec![0.25])
+            .with_trials(1)
+            .run();
+
+        let mut csv = Vec::new();
+        diagram.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "delta,rho,success");
+        assert!(lines[1].starts_with("0.5,0.25,"));
+        assert!(lines[2].starts_with("1,0.25,"));
+    }
+}
@@ -0,0 +1,106 @@
+use std::sync::{Arc, Mutex, OnceLock};
+
+use rustdct::{DctPlanner, TransformType2And3};
+use rustfft::{Fft, FftDirection, FftPlanner};
+
+/// Thread-safe cache of FFT and DCT plans, shared by all [`Transformation`] constructors and
+/// fast operators, see [`shared`].
+///
+/// Planning a size is done once, later requests for it return the same plan.
+///
+/// [`Transformation`]: crate::Transformation
+pub struct PlannerCache {
+    fft: Mutex<FftPlanner<f64>>,
+    dct: Mutex<DctPlanner<f64>>,
+}
+
+impl PlannerCache {
+    pub fn new() -> Self {
+        Self {
+            fft: Mutex::new(FftPlanner::new()),
+            dct: Mutex::new(DctPlanner::new()),
+        }
+    }
+
+    pub fn fft(&self, len: usize, direction: FftDirection) -> Arc<dyn Fft<f64>> {
+        self.fft
+            .lock()
+            .expect("FFT planner lock is poisoned")
+            .plan_fft(len, direction)
+    }
+
+    /// DCT-II, whose plan also computes the DCT-III.
+    pub fn dct2(&self, len: usize) -> Arc<dyn TransformType2And3<f64>> {
+        self.dct
+            .lock()
+            .expect("DCT planner lock is poisoned")
+            .plan_dct2(len)
+    }
+
+    /// DCT-III, the inverse of the DCT-II up to scaling.
+    pub fn dct3(&self, len: usize) -> Arc<dyn TransformType2And3<f64>> {
+        self.dct
+            .lock()
+            .expect("DCT planner lock is poisoned")
+            .plan_dct3(len)
+    }
+
+    /// Plans the FFTs in both directions and the DCTs of each length ahead of time, e.g. before
+    /// building many models of the same size from several threads.
+    pub fn prewarm(&self, lens: &[usize]) {
+        for &len in lens {
+            self.fft(len, FftDirection::Forward);
+            self.fft(len, FftDirection::Inverse);
+            self.dct2(len);
+            self.dct3(len);
+        }
+    }
+}
+
+impl Default for PlannerCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Process wide planner cache used by the crate.
+pub fn shared() -> &'static PlannerCache {
+    static CACHE: OnceLock<PlannerCache> = OnceLock::new();
+    CACHE.get_or_init(PlannerCache::new)
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use rustfft::FftDirection;
+
+    use super::{shared, PlannerCache};
+
+    #[test]
+    fn plans_are_reused() {
+        let cache = PlannerCache::new();
+        assert!(Arc::ptr_eq(
+            &cache.fft(12, FftDirection::Forward),
+            &cache.fft(12, FftDirection::Forward)
+        ));
+        assert!(Arc::ptr_eq(&cache.dct2(12), &cache.dct2(12)));
+        assert_eq!(cache.dct3(7).len(), 7);
+    }
+
+    #[test]
+    fn shared_across_threads() {
+        shared().prewarm(&[24]);
+        let expected = shared().fft(24, FftDirection::Inverse);
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    assert!(Arc::ptr_eq(
+                        &shared().fft(24, FftDirection::Inverse),
+                        &expected
+                    ));
+                });
+            }
+        });
+    }
+}
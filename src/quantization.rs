@@ -0,0 +1,383 @@
+/// Scalar quantization of the measurements to a fixed number of bits per measurement.
+///
+/// Values outside of the range are clipped to the outermost cells.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Quantizer {
+    /// equally spaced cells over [-range, range]
+    Uniform { bits: u8, range: QuantizationRange },
+    /// µ-law companding, i.e. finer cells close to zero and coarser ones towards the range limits
+    MuLaw {
+        bits: u8,
+        range: QuantizationRange,
+        mu: f64,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QuantizationRange {
+    Fixed(f64),
+    /// maximum absolute value of the quantized measurements, transmitted along with them
+    Adaptive,
+}
+
+/// Cell indices packed into `bits` bits each, together with the quantization range used.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuantizedMeasurements {
+    bytes: Vec<u8>,
+    len: usize,
+    bits: u8,
+    range: f64,
+}
+
+/// Bounds of a quantization cell, clipped cells are unbounded towards the outside.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cell {
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl Quantizer {
+    pub fn bits(&self) -> u8 {
+        match self {
+            Quantizer::Uniform { bits, .. } | Quantizer::MuLaw { bits, .. } => *bits,
+        }
+    }
+
+    fn range(&self) -> QuantizationRange {
+        match self {
+            Quantizer::Uniform { range, .. } | Quantizer::MuLaw { range, .. } => *range,
+        }
+    }
+
+    fn levels(&self) -> usize {
+        1 << self.bits()
+    }
+
+    // same cells up to the range, which is transmitted with the quantized measurements
+    pub(crate) fn is_compatible(&self, other: &Quantizer) -> bool {
+        self.bits() == other.bits()
+            && match (self, other) {
+                (Quantizer::Uniform { .. }, Quantizer::Uniform { .. }) => true,
+                (Quantizer::MuLaw { mu: a, .. }, Quantizer::MuLaw { mu: b, .. }) => a == b,
+                _ => false,
+            }
+    }
+
+    pub fn quantize(&self, measurements: &[f64]) -> QuantizedMeasurements {
+        assert!(
+            (1..=16).contains(&self.bits()),
+            "{} bits per measurement, must be between 1 and 16",
+            self.bits()
+        );
+        if let Quantizer::MuLaw { mu, .. } = self {
+            assert!(
+                mu.is_finite() && *mu > 0.0,
+                "µ-law parameter {mu} must be positive"
+            );
+        }
+        let range = match self.range() {
+            QuantizationRange::Fixed(range) => {
+                assert!(
+                    range.is_finite() && range > 0.0,
+                    "quantization range {range} must be positive"
+                );
+                range
+            }
+            // floored, so that all zero measurements keep a valid range
+            QuantizationRange::Adaptive => measurements
+                .iter()
+                .fold(f64::MIN_POSITIVE, |a, m| m.abs().max(a)),
+        };
+
+        let levels = self.levels();
+        let mut writer = BitWriter::default();
+        for m in measurements {
+            let normalized = self.compress((m / range).clamp(-1.0, 1.0));
+            let idx = (((normalized + 1.0) / 2.0 * levels as f64) as usize).min(levels - 1);
+            writer.write(idx as u32, self.bits());
+        }
+
+        QuantizedMeasurements {
+            bytes: writer.into_bytes(),
+            len: measurements.len(),
+            bits: self.bits(),
+            range,
+        }
+    }
+
+    /// Reconstructs the measurements as the centers of their cells.
+    pub fn dequantize(&self, quantized: &QuantizedMeasurements) -> Vec<f64> {
+        let levels = self.levels() as f64;
+        quantized
+            .indices()
+            .into_iter()
+            .map(|idx| {
+                let center = (idx as f64 + 0.5) / levels * 2.0 - 1.0;
+                self.expand(center) * quantized.range
+            })
+            .collect()
+    }
+
+    pub fn cells(&self, quantized: &QuantizedMeasurements) -> Vec<Cell> {
+        let levels = self.levels();
+        let boundary = |idx: usize| {
+            let normalized = idx as f64 / levels as f64 * 2.0 - 1.0;
+            self.expand(normalized) * quantized.range
+        };
+        quantized
+            .indices()
+            .into_iter()
+            .map(|idx| Cell {
+                lower: if idx == 0 {
+                    f64::NEG_INFINITY
+                } else {
+                    boundary(idx)
+                },
+                upper: if idx + 1 == levels {
+                    f64::INFINITY
+                } else {
+                    boundary(idx + 1)
+                },
+            })
+            .collect()
+    }
+
+    /// Standard deviation of the quantization error, assuming it is uniform within each cell.
+    pub fn noise_sigma(&self, quantized: &QuantizedMeasurements) -> f64 {
+        let widths: Vec<f64> = self
+            .cells(quantized)
+            .iter()
+            .map(|c| c.upper - c.lower)
+            .filter(|w| w.is_finite())
+            .collect();
+        if widths.is_empty() {
+            // every measurement clipped, fall back to the width of a uniform cell
+            return 2.0 * quantized.range / self.levels() as f64 / 12.0_f64.sqrt();
+        }
+        (widths.iter().map(|w| w * w).sum::<f64>() / widths.len() as f64 / 12.0).sqrt()
+    }
+
+    // companding on [-1, 1]
+    fn compress(&self, value: f64) -> f64 {
+        match self {
+            Quantizer::Uniform { .. } => value,
+            Quantizer::MuLaw { mu, .. } => value.signum() * (mu * value.abs()).ln_1p() / mu.ln_1p(),
+        }
+    }
+
+    fn expand(&self, value: f64) -> f64 {
+        match self {
+            Quantizer::Uniform { .. } => value,
+            Quantizer::MuLaw { mu, .. } => {
+                value.signum() * ((1.0 + mu).powf(value.abs()) - 1.0) / mu
+            }
+        }
+    }
+}
+
+impl QuantizedMeasurements {
+    pub fn from_bytes(bytes: Vec<u8>, len: usize, bits: u8, range: f64) -> Self {
+        assert!(
+            (1..=16).contains(&bits),
+            "{bits} bits per measurement, must be between 1 and 16"
+        );
+        assert!(
+            len.checked_mul(bits as usize)
+                .is_some_and(|total| total.div_ceil(8) <= bytes.len()),
+            "{} bytes can't hold {len} measurements of {bits} bits",
+            bytes.len()
+        );
+        Self {
+            bytes,
+            len,
+            bits,
+            range,
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    pub fn range(&self) -> f64 {
+        self.range
+    }
+
+    pub fn indices(&self) -> Vec<usize> {
+        let mut reader = BitReader::new(&self.bytes);
+        (0..self.len)
+            .map(|_| reader.read(self.bits) as usize)
+            .collect()
+    }
+}
+
+/// Writes values with a given number of bits, least significant bit first.
+#[derive(Default)]
+pub(crate) struct BitWriter {
+    bytes: Vec<u8>,
+    nbits: usize,
+}
+
+impl BitWriter {
+    pub(crate) fn write(&mut self, value: u32, bits: u8) {
+        for bit in 0..bits {
+            if self.nbits.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if value & (1 << bit) != 0 {
+                *self.bytes.last_mut().unwrap() |= 1 << (self.nbits % 8);
+            }
+            self.nbits += 1;
+        }
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+pub(crate) struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    /// Number of bits read so far, including those past the end.
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Reads `bits` bits, reading past the end yields zeros.
+    pub(crate) fn read(&mut self, bits: u8) -> u32 {
+        let mut value = 0;
+        for bit in 0..bits {
+            let byte = self.bytes.get(self.position / 8).copied().unwrap_or(0);
+            if byte & (1 << (self.position % 8)) != 0 {
+                value |= 1 << bit;
+            }
+            self.position += 1;
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use super::{BitReader, BitWriter, QuantizationRange, QuantizedMeasurements, Quantizer};
+
+    #[test]
+    fn bits_roundtrip() {
+        let mut writer = BitWriter::default();
+        writer.write(5, 3);
+        writer.write(1023, 10);
+        writer.write(0, 2);
+        writer.write(1, 1);
+        let bytes = writer.into_bytes();
+        assert_eq!(bytes.len(), 2);
+
+        let mut reader = BitReader::new(&bytes);
+        assert_eq!(reader.read(3), 5);
+        assert_eq!(reader.read(10), 1023);
+        assert_eq!(reader.read(2), 0);
+        assert_eq!(reader.read(1), 1);
+    }
+
+    #[test]
+    fn uniform_quantization() {
+        let quantizer = Quantizer::Uniform {
+            bits: 2,
+            range: QuantizationRange::Fixed(1.0),
+        };
+        let quantized = quantizer.quantize(&[-0.9, -0.1, 0.2, 0.6, 3.0]);
+
+        assert_eq!(quantized.indices(), vec![0, 1, 2, 3, 3]);
+        assert_eq!(quantized.as_bytes().len(), 2);
+        assert_eq!(
+            quantizer.dequantize(&quantized),
+            vec![-0.75, -0.25, 0.25, 0.75, 0.75]
+        );
+
+        let cells = quantizer.cells(&quantized);
+        assert_eq!(cells[0].lower, f64::NEG_INFINITY);
+        assert_eq!(cells[0].upper, -0.5);
+        assert_eq!((cells[1].lower, cells[1].upper), (-0.5, 0.0));
+        assert_eq!(cells[4].upper, f64::INFINITY);
+        assert_relative_eq!(quantizer.noise_sigma(&quantized), 0.5 / 12.0_f64.sqrt());
+    }
+
+    #[test]
+    fn adaptive_range() {
+        let quantizer = Quantizer::Uniform {
+            bits: 4,
+            range: QuantizationRange::Adaptive,
+        };
+        let measurements = [-2.0, 0.5, 1.5];
+        let quantized = quantizer.quantize(&measurements);
+
+        assert_eq!(quantized.range(), 2.0);
+        for (m, d) in measurements.iter().zip(quantizer.dequantize(&quantized)) {
+            assert!((m - d).abs() <= 2.0 / 16.0);
+        }
+    }
+
+    #[test]
+    fn mu_law_has_finer_cells_around_zero() {
+        let quantizer = Quantizer::MuLaw {
+            bits: 4,
+            range: QuantizationRange::Fixed(1.0),
+            mu: 255.0,
+        };
+        let quantized = quantizer.quantize(&[0.01, 0.9]);
+        let cells = quantizer.cells(&quantized);
+
+        for (m, c) in [0.01, 0.9].iter().zip(cells.iter()) {
+            assert!(c.lower <= *m && *m <= c.upper);
+        }
+        assert!(cells[0].upper - cells[0].lower < cells[1].upper - cells[1].lower);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't hold")]
+    fn rejects_too_few_bytes() {
+        QuantizedMeasurements::from_bytes(vec![0; 2], usize::MAX, 4, 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be between 1 and 16")]
+    fn rejects_zero_bits() {
+        let quantizer = Quantizer::Uniform {
+            bits: 0,
+            range: QuantizationRange::Fixed(1.0),
+        };
+        quantizer.quantize(&[0.5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be positive")]
+    fn rejects_non_positive_mu() {
+        let quantizer = Quantizer::MuLaw {
+            bits: 4,
+            range: QuantizationRange::Fixed(1.0),
+            mu: 0.0,
+        };
+        quantizer.quantize(&[0.5]);
+    }
+}
@@ -0,0 +1,124 @@
+//! Decoding of sequences of frames, e.g. from a slowly varying sensor signal.
+
+use nalgebra::{DMatrix, DVectorView};
+
+use crate::{
+    algorithm::{KalmanFilteredCsSolver, KalmanState, WarmStart},
+    complex::ComplexFields,
+    matrix::Matrix,
+    precision::{Complex64, Precision},
+    Model,
+};
+
+/// Decodes frames in order, warm starting each one with the support of the previous frame.
+///
+/// See [`Model::decompress_warm`], the decoder only keeps the support between frames.
+pub struct SequentialDecoder<'a> {
+    model: &'a Model,
+    support: Option<Vec<usize>>,
+}
+
+impl<'a> SequentialDecoder<'a> {
+    pub fn new(model: &'a Model) -> Self {
+        Self {
+            model,
+            support: None,
+        }
+    }
+
+    pub fn decode<T>(&mut self, compressed: T) -> Vec<f64>
+    where
+        T: AsRef<[f64]>,
+    {
+        let warm_start = self.support.take().map(WarmStart::Support);
+        let reconstruction = self.model.decompress_warm(compressed, warm_start.as_ref());
+        self.support = Some(reconstruction.support);
+        reconstruction.signal
+    }
+
+    /// Support of the last decoded frame.
+    pub fn support(&self) -> Option<&[usize]> {
+        self.support.as_deref()
+    }
+
+    /// Forgets the previous frame, e.g. after a scene cut.
+    pub fn reset(&mut self) {
+        self.support = None;
+    }
+}
+
+/// Dynamic compressed sensing decoder, tracking the support of the coefficients and Kalman
+/// filtering them from frame to frame, see [`KalmanFilteredCsSolver`].
+///
+/// Works on the coefficients in the transformation basis, without the column normalization
+/// of the model, so that the thresholds and variances of the solver refer to them.
+pub struct DynamicDecoder<'a> {
+    model: &'a Model,
+    solver: KalmanFilteredCsSolver,
+    sensing_matrix: Matrix,
+    real: KalmanState<f64>,
+    complex: KalmanState<Complex64>,
+}
+
+impl<'a> DynamicDecoder<'a> {
+    pub fn new(model: &'a Model, solver: KalmanFilteredCsSolver) -> Self {
+        let sensing_matrix = match &model.sensing_matrix {
+            Matrix::Identity(dim) => Matrix::Identity(*dim),
+            Matrix::Real(m) => Matrix::Real(unnormalize(m, &model.column_norms)),
+            Matrix::Complex(m) => Matrix::Complex(unnormalize(m, &model.column_norms)),
+        };
+        Self {
+            model,
+            solver,
+            sensing_matrix,
+            real: KalmanState::new(),
+            complex: KalmanState::new(),
+        }
+    }
+
+    pub fn decode<T>(&mut self, compressed: T) -> Vec<f64>
+    where
+        T: AsRef<[f64]>,
+    {
+        let compressed = compressed.as_ref();
+        let y = DVectorView::from_slice(compressed, compressed.len());
+        match &self.sensing_matrix {
+            Matrix::Identity(_) => compressed.to_vec(),
+            Matrix::Real(m) => {
+                self.solver.step(&y, m, &mut self.real);
+                &self.model.transform * self.real.coefficients(m.ncols()).as_slice()
+            }
+            Matrix::Complex(m) => {
+                self.solver.step(&y, m, &mut self.complex);
+                let coefficients = self.complex.coefficients(m.ncols());
+                (&self.model.transform * coefficients.as_slice()).real()
+            }
+        }
+    }
+
+    /// Currently tracked support, in the order the coefficients were detected.
+    pub fn support(&self) -> &[usize] {
+        match &self.sensing_matrix {
+            Matrix::Complex(_) => &self.complex.support,
+            _ => &self.real.support,
+        }
+    }
+
+    /// Forgets the tracked support, e.g. after a scene cut.
+    pub fn reset(&mut self) {
+        self.real = KalmanState::new();
+        self.complex = KalmanState::new();
+    }
+}
+
+// scales the unit norm columns back to their original norms
+fn unnormalize<P>(matrix: &DMatrix<P>, column_norms: &[f64]) -> DMatrix<P>
+where
+    P: Precision,
+{
+    let mut matrix = matrix.clone();
+    for (mut column, norm) in matrix.column_iter_mut().zip(column_norms) {
+        column *= nalgebra::convert::<f64, P>(*norm);
+    }
+    matrix
+}
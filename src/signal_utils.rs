@@ -1,60 +1,547 @@
 use std::f64::consts::PI;
 
+use num_complex::Complex64;
 use rand::{
     distributions::{uniform::SampleUniform, Uniform},
+    seq::index,
     Rng,
 };
+use rand_distr::StandardNormal;
 
-pub fn diff(original: &Vec<f64>, decompressed: &Vec<f64>) -> Vec<f64> {
+use crate::Transformation;
+
+pub fn diff(original: &[f64], decompressed: &[f64]) -> Vec<f64> {
+    assert_same_length(original, decompressed);
     original
         .iter()
         .zip(decompressed.iter())
-        .map(|(a, b)| (a - b))
+        .map(|(a, b)| a - b)
         .collect()
 }
 
-pub fn error_l2(original: &Vec<f64>, decompressed: &Vec<f64>) -> f64 {
+/// Squared l2 norm of the reconstruction error.
+pub fn error_l2(original: &[f64], decompressed: &[f64]) -> f64 {
+    assert_same_length(original, decompressed);
     original
         .iter()
         .zip(decompressed.iter())
         .map(|(a, b)| (a - b).powi(2))
         .sum()
 }
-pub fn error_l2_norm(original: &Vec<f64>, decompressed: &Vec<f64>) -> f64 {
+
+/// Same as [`mse`], kept for compatibility.
+pub fn error_l2_norm(original: &[f64], decompressed: &[f64]) -> f64 {
+    assert_same_length(original, decompressed);
+    mse(original, decompressed)
+}
+
+/// Mean squared error.
+pub fn mse(original: &[f64], decompressed: &[f64]) -> f64 {
+    assert_same_length(original, decompressed);
     error_l2(original, decompressed) / (original.len() as f64)
 }
 
+/// Normalized mean squared error, i.e. error energy relative to the signal energy.
+pub fn nmse(original: &[f64], decompressed: &[f64]) -> f64 {
+    assert_same_length(original, decompressed);
+    error_l2(original, decompressed) / energy(original)
+}
+
+/// l2 norm of the error relative to the l2 norm of the original signal.
+pub fn relative_error_l2(original: &[f64], decompressed: &[f64]) -> f64 {
+    assert_same_length(original, decompressed);
+    nmse(original, decompressed).sqrt()
+}
+
+pub fn max_abs_error(original: &[f64], decompressed: &[f64]) -> f64 {
+    assert_same_length(original, decompressed);
+    original
+        .iter()
+        .zip(decompressed.iter())
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0, f64::max)
+}
+
+/// Signal to noise ratio in dB, the reconstruction error is treated as noise.
+pub fn snr_db(original: &[f64], decompressed: &[f64]) -> f64 {
+    assert_same_length(original, decompressed);
+    -10.0 * nmse(original, decompressed).log10()
+}
+
+/// Peak signal to noise ratio in dB, the peak is the maximum absolute value of the original.
+pub fn psnr_db(original: &[f64], decompressed: &[f64]) -> f64 {
+    assert_same_length(original, decompressed);
+    let peak = original.iter().map(|a| a.abs()).fold(0.0, f64::max);
+    10.0 * (peak.powi(2) / mse(original, decompressed)).log10()
+}
+
+/// Indices of all entries with an absolute value above `threshold`.
+pub fn support(signal: &[f64], threshold: f64) -> Vec<usize> {
+    signal
+        .iter()
+        .enumerate()
+        .filter(|(_, a)| a.abs() > threshold)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Fraction of the recovered support which is part of the original support.
+pub fn support_precision(original: &[f64], decompressed: &[f64], threshold: f64) -> f64 {
+    assert_same_length(original, decompressed);
+    let recovered = support(decompressed, threshold);
+    if recovered.is_empty() {
+        return 1.0;
+    }
+    support_hits(original, decompressed, threshold) as f64 / recovered.len() as f64
+}
+
+/// Fraction of the original support which has been recovered.
+pub fn support_recall(original: &[f64], decompressed: &[f64], threshold: f64) -> f64 {
+    assert_same_length(original, decompressed);
+    let expected = support(original, threshold);
+    if expected.is_empty() {
+        return 1.0;
+    }
+    support_hits(original, decompressed, threshold) as f64 / expected.len() as f64
+}
+
+pub fn exact_support_recovery(original: &[f64], decompressed: &[f64], threshold: f64) -> bool {
+    assert_same_length(original, decompressed);
+    support(original, threshold) == support(decompressed, threshold)
+}
+
+// entries above the threshold in both signals
+fn support_hits(original: &[f64], decompressed: &[f64], threshold: f64) -> usize {
+    support(decompressed, threshold)
+        .iter()
+        .filter(|&&i| original[i].abs() > threshold)
+        .count()
+}
+
+// a length mismatch would otherwise be cut off by zip
+fn assert_same_length(original: &[f64], decompressed: &[f64]) {
+    assert_eq!(
+        original.len(),
+        decompressed.len(),
+        "original and decompressed signal must have the same length"
+    );
+}
+
+fn energy(signal: &[f64]) -> f64 {
+    signal.iter().map(|a| a.powi(2)).sum()
+}
+
+/// Distribution of the non-zero entries of generated sparse signals.
+#[derive(Clone, Copy, Debug)]
+pub enum Amplitude {
+    /// uniform in [0, 1)
+    Uniform,
+    /// standard normal
+    Gaussian,
+    /// +1 or -1 with equal probability
+    Rademacher,
+}
+
+impl Amplitude {
+    fn sample<R>(self, rng: &mut R) -> f64
+    where
+        R: Rng,
+    {
+        match self {
+            Amplitude::Uniform => rng.gen_range(0.0..1.0),
+            Amplitude::Gaussian => rng.sample(StandardNormal),
+            Amplitude::Rademacher => {
+                if rng.gen() {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+        }
+    }
+}
+
 pub fn generate_cos_signal(len: usize, sparsity: usize) -> Vec<f64> {
-    let frequencies = random_values(sparsity, 0.0, 10.0);
-    let amplitudes = random_values(sparsity, 0.0, 1.0);
-    let params: Vec<(f64, f64)> = frequencies.zip(amplitudes).collect();
+    generate_cos_signal_with_rng(len, sparsity, &mut rand::thread_rng())
+}
+
+pub fn generate_cos_signal_with_rng<R>(len: usize, sparsity: usize, rng: &mut R) -> Vec<f64>
+where
+    R: Rng,
+{
+    let frequencies = random_values(sparsity, 0.0, 10.0, rng);
+    let amplitudes = random_values(sparsity, 0.0, 1.0, rng);
+    let params: Vec<(f64, f64)> = frequencies.into_iter().zip(amplitudes).collect();
     (0..len)
         .map(|i| i as f64 / len as f64)
         .map(|i| {
             params
                 .iter()
-                .map(|(f, a)| (a * (i * 2.0 * PI * f).cos()))
+                .map(|(f, a)| a * (i * 2.0 * PI * f).cos())
                 .sum()
         })
         .collect()
 }
 
 pub fn generate_sparse_signal(len: usize, sparsity: usize) -> Vec<f64> {
-    let indices = random_values(sparsity, 0, len);
-    let amplitudes = random_values(sparsity, 0.0, 1.0);
+    generate_sparse_signal_with_rng(len, sparsity, &mut rand::thread_rng())
+}
 
+/// Signal with exactly `sparsity` non-zero entries, uniformly distributed in [0, 1).
+pub fn generate_sparse_signal_with_rng<R>(len: usize, sparsity: usize, rng: &mut R) -> Vec<f64>
+where
+    R: Rng,
+{
+    generate_exact_sparse_signal(len, sparsity, Amplitude::Uniform, rng)
+}
+
+/// Signal with exactly `sparsity` non-zero entries at distinct random positions.
+pub fn generate_exact_sparse_signal<R>(
+    len: usize,
+    sparsity: usize,
+    amplitude: Amplitude,
+    rng: &mut R,
+) -> Vec<f64>
+where
+    R: Rng,
+{
+    assert!(
+        sparsity <= len,
+        "sparsity {sparsity} exceeds the signal length {len}"
+    );
     let mut signal = vec![0.0; len];
-    for (i, a) in indices.zip(amplitudes) {
-        signal[i] = a;
+    for i in index::sample(rng, len, sparsity).into_vec() {
+        signal[i] = nonzero(amplitude, rng);
     }
     signal
 }
 
-fn random_values<T>(n: usize, min: T, max: T) -> impl Iterator<Item = T>
+/// Complex signal with exactly `sparsity` non-zero, circularly symmetric gaussian entries.
+pub fn generate_exact_sparse_complex_signal<R>(
+    len: usize,
+    sparsity: usize,
+    rng: &mut R,
+) -> Vec<Complex64>
+where
+    R: Rng,
+{
+    assert!(
+        sparsity <= len,
+        "sparsity {sparsity} exceeds the signal length {len}"
+    );
+    let mut signal = vec![Complex64::new(0.0, 0.0); len];
+    for i in index::sample(rng, len, sparsity).into_vec() {
+        let re: f64 = rng.sample(StandardNormal);
+        let im: f64 = rng.sample(StandardNormal);
+        signal[i] = Complex64::new(re, im) * std::f64::consts::FRAC_1_SQRT_2;
+    }
+    signal
+}
+
+/// Compressible signal, whose sorted magnitudes decay like a power law `i^(-decay)`.
+/// Signs and positions of the entries are random.
+pub fn generate_compressible_signal<R>(len: usize, decay: f64, rng: &mut R) -> Vec<f64>
+where
+    R: Rng,
+{
+    let mut signal = vec![0.0; len];
+    for (rank, i) in index::sample(rng, len, len).into_iter().enumerate() {
+        let sign = Amplitude::Rademacher.sample(rng);
+        signal[i] = sign * ((rank + 1) as f64).powf(-decay);
+    }
+    signal
+}
+
+/// Signal consisting of `len / block_len` contiguous blocks, of which `blocks` are non-zero.
+pub fn generate_block_sparse_signal<R>(
+    len: usize,
+    block_len: usize,
+    blocks: usize,
+    amplitude: Amplitude,
+    rng: &mut R,
+) -> Vec<f64>
+where
+    R: Rng,
+{
+    assert!(block_len > 0, "block length must be positive");
+    assert!(
+        blocks <= len / block_len,
+        "{blocks} blocks exceed the {} blocks of the signal",
+        len / block_len
+    );
+    let mut signal = vec![0.0; len];
+    for block in index::sample(rng, len / block_len, blocks).into_vec() {
+        for entry in signal.iter_mut().skip(block * block_len).take(block_len) {
+            *entry = nonzero(amplitude, rng);
+        }
+    }
+    signal
+}
+
+/// Signal which is exactly `sparsity` sparse in the basis given by `transformation`,
+/// i.e. the transformation (synthesis) matrix applied to a sparse coefficient vector.
+/// The coefficient vector has one entry per dictionary atom, which may be more than `len`.
+/// For complex transformations only the real part is returned.
+pub fn generate_sparse_in_basis<R>(
+    len: usize,
+    sparsity: usize,
+    transformation: Transformation,
+    amplitude: Amplitude,
+    rng: &mut R,
+) -> Vec<f64>
+where
+    R: Rng,
+{
+    let matrix = transformation.into_matrix(len);
+    let coefficients =
+        generate_exact_sparse_signal(matrix.dimension().ncols, sparsity, amplitude, rng);
+    &matrix * coefficients.as_slice()
+}
+
+/// Frames of a slowly varying sparse signal, each with exactly `sparsity` non-zero entries.
+///
+/// From frame to frame `changes` entries move to new random positions with new amplitudes,
+/// while the others random walk with gaussian steps of standard deviation `drift`.
+pub fn generate_sparse_sequence<R>(
+    len: usize,
+    sparsity: usize,
+    frames: usize,
+    changes: usize,
+    drift: f64,
+    amplitude: Amplitude,
+    rng: &mut R,
+) -> Vec<Vec<f64>>
+where
+    R: Rng,
+{
+    let mut signal = generate_exact_sparse_signal(len, sparsity, amplitude, rng);
+    let mut sequence = Vec::with_capacity(frames);
+    for _ in 0..frames {
+        sequence.push(signal.clone());
+
+        let active = support(&signal, 0.0);
+        for a in active.iter() {
+            signal[*a] += drift * rng.sample::<f64, _>(StandardNormal);
+        }
+        let inactive: Vec<usize> = (0..len).filter(|i| signal[*i] == 0.0).collect();
+        let removed = index::sample(rng, active.len(), changes.min(active.len()));
+        let added = index::sample(rng, inactive.len(), changes.min(inactive.len()));
+        for (r, a) in removed.into_iter().zip(added) {
+            signal[active[r]] = 0.0;
+            signal[inactive[a]] = nonzero(amplitude, rng);
+        }
+    }
+    sequence
+}
+
+/// Adds white gaussian noise, so that the result has the given signal to noise ratio in dB.
+pub fn add_noise<R>(signal: &[f64], snr_db: f64, rng: &mut R) -> Vec<f64>
+where
+    R: Rng,
+{
+    let signal_power = energy(signal) / signal.len() as f64;
+    let sigma = (signal_power / 10.0_f64.powf(snr_db / 10.0)).sqrt();
+    signal
+        .iter()
+        .map(|a| a + sigma * rng.sample::<f64, _>(StandardNormal))
+        .collect()
+}
+
+// non-zero sample of the given amplitude distribution
+fn nonzero<R>(amplitude: Amplitude, rng: &mut R) -> f64
+where
+    R: Rng,
+{
+    loop {
+        let a = amplitude.sample(rng);
+        if a != 0.0 {
+            return a;
+        }
+    }
+}
+
+fn random_values<T, R>(n: usize, min: T, max: T, rng: &mut R) -> Vec<T>
 where
     T: SampleUniform,
+    R: Rng,
 {
-    rand::thread_rng()
-        .sample_iter(Uniform::new(min, max))
-        .take(n)
+    rng.sample_iter(Uniform::new(min, max)).take(n).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::Transformation;
+
+    const ORIGINAL: [f64; 4] = [1.0, 0.0, -2.0, 0.0];
+    const DECOMPRESSED: [f64; 4] = [1.0, 0.5, -1.0, 0.0];
+
+    #[test]
+    fn mean_squared_errors() {
+        assert_relative_eq!(error_l2(&ORIGINAL, &DECOMPRESSED), 1.25);
+        assert_relative_eq!(mse(&ORIGINAL, &DECOMPRESSED), 0.3125);
+        assert_relative_eq!(error_l2_norm(&ORIGINAL, &DECOMPRESSED), 0.3125);
+        assert_relative_eq!(nmse(&ORIGINAL, &DECOMPRESSED), 0.25);
+        assert_relative_eq!(relative_error_l2(&ORIGINAL, &DECOMPRESSED), 0.5);
+    }
+
+    #[test]
+    fn max_abs() {
+        assert_relative_eq!(max_abs_error(&ORIGINAL, &DECOMPRESSED), 1.0);
+    }
+
+    #[test]
+    fn snr_and_psnr() {
+        assert_relative_eq!(snr_db(&ORIGINAL, &DECOMPRESSED), 6.0206, epsilon = 1e-4);
+        // peak 2, mse 0.3125
+        assert_relative_eq!(psnr_db(&ORIGINAL, &DECOMPRESSED), 11.0721, epsilon = 1e-4);
+        assert!(snr_db(&ORIGINAL, &ORIGINAL).is_infinite());
+    }
+
+    #[test]
+    fn support_recovery() {
+        assert_eq!(support(&DECOMPRESSED, 0.1), vec![0, 1, 2]);
+        assert_relative_eq!(support_precision(&ORIGINAL, &DECOMPRESSED, 0.1), 2.0 / 3.0);
+        assert_relative_eq!(support_recall(&ORIGINAL, &DECOMPRESSED, 0.1), 1.0);
+        assert!(!exact_support_recovery(&ORIGINAL, &DECOMPRESSED, 0.1));
+        assert!(exact_support_recovery(&ORIGINAL, &DECOMPRESSED, 0.6));
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn support_recovery_of_different_lengths() {
+        support_precision(&ORIGINAL, &[1.0, 0.0, -2.0, 0.0, 3.0], 0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn empty_support_of_different_lengths() {
+        support_recall(&[0.0; 3], &[0.0; 2], 0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn exact_support_recovery_of_different_lengths() {
+        exact_support_recovery(&ORIGINAL, &[0.0], 0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn error_of_different_lengths() {
+        max_abs_error(&ORIGINAL, &[1.0, 0.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn snr_of_different_lengths() {
+        snr_db(&[1.0, 2.0], &ORIGINAL);
+    }
+
+    #[test]
+    fn exact_sparsity() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for amplitude in [
+            Amplitude::Uniform,
+            Amplitude::Gaussian,
+            Amplitude::Rademacher,
+        ] {
+            let signal = generate_exact_sparse_signal(16, 8, amplitude, &mut rng);
+            assert_eq!(support(&signal, 0.0).len(), 8);
+        }
+        let rademacher = generate_exact_sparse_signal(16, 4, Amplitude::Rademacher, &mut rng);
+        assert!(rademacher.iter().all(|a| [-1.0, 0.0, 1.0].contains(a)));
+
+        let complex = generate_exact_sparse_complex_signal(16, 5, &mut rng);
+        assert_eq!(complex.iter().filter(|a| a.norm() > 0.0).count(), 5);
+    }
+
+    #[test]
+    fn generators_are_reproducible_with_seed() {
+        let generate = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (
+                generate_cos_signal_with_rng(16, 3, &mut rng),
+                generate_sparse_signal_with_rng(16, 3, &mut rng),
+            )
+        };
+        assert_eq!(generate(1), generate(1));
+        assert_ne!(generate(1), generate(2));
+    }
+
+    #[test]
+    fn compressible_magnitudes_decay() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let signal = generate_compressible_signal(8, 1.0, &mut rng);
+
+        let mut magnitudes: Vec<f64> = signal.iter().map(|a| a.abs()).collect();
+        magnitudes.sort_by(|a, b| b.partial_cmp(a).unwrap());
+        let expected: Vec<f64> = (1..=8).map(|i| 1.0 / i as f64).collect();
+        assert_eq!(magnitudes, expected);
+    }
+
+    #[test]
+    fn block_sparsity() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let signal = generate_block_sparse_signal(12, 3, 2, Amplitude::Gaussian, &mut rng);
+
+        let blocks: Vec<bool> = signal
+            .chunks(3)
+            .map(|block| block.iter().all(|a| *a != 0.0))
+            .collect();
+        assert_eq!(blocks.iter().filter(|b| **b).count(), 2);
+        assert_eq!(support(&signal, 0.0).len(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceed")]
+    fn too_many_blocks() {
+        generate_block_sparse_signal(12, 3, 5, Amplitude::Gaussian, &mut StdRng::seed_from_u64(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the signal length")]
+    fn sparsity_above_length() {
+        generate_exact_sparse_signal(4, 5, Amplitude::Gaussian, &mut StdRng::seed_from_u64(0));
+    }
+
+    #[test]
+    fn sparse_sequence_changes_slowly() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let sequence = generate_sparse_sequence(64, 8, 10, 1, 0.01, Amplitude::Gaussian, &mut rng);
+
+        assert_eq!(sequence.len(), 10);
+        for frames in sequence.windows(2) {
+            let previous = support(&frames[0], 0.0);
+            let current = support(&frames[1], 0.0);
+            assert_eq!(current.len(), 8);
+            assert_eq!(current.iter().filter(|i| previous.contains(i)).count(), 7);
+        }
+    }
+
+    #[test]
+    fn sparse_in_dct() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let signal = generate_sparse_in_basis(
+            16,
+            2,
+            Transformation::Dct1dInverse,
+            Amplitude::Gaussian,
+            &mut rng,
+        );
+
+        let coefficients = &Transformation::Dct1d.into_matrix(16) * signal.as_slice();
+        assert_eq!(support(&coefficients, 1e-9).len(), 2);
+    }
+
+    #[test]
+    fn noise_with_snr() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let signal = generate_cos_signal_with_rng(4096, 4, &mut rng);
+        let noisy = add_noise(&signal, 20.0, &mut rng);
+
+        assert_relative_eq!(snr_db(&signal, &noisy), 20.0, epsilon = 0.5);
+    }
 }
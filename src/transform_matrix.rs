@@ -1,21 +1,53 @@
-use std::ops::Div;
-
-use nalgebra::{dimension, DMatrix};
-use rustdct::DctPlanner;
-use rustfft::{num_complex::Complex64, FftDirection, FftPlanner};
-
-use crate::matrix::{ComplexMatrix, Dimension, Matrix, RealMatrix};
-
-#[derive(Clone, Copy)]
+use std::sync::Arc;
+
+use nalgebra::DMatrix;
+use rustfft::{num_complex::Complex64, FftDirection};
+use simba::scalar::SubsetOf;
+
+use crate::{
+    gabor::GaborFrame,
+    matrix::{ComplexMatrix, Dimension, Matrix, RealMatrix},
+    planner,
+    precision::Precision,
+};
+
+/// Not `Copy`, as learned dictionaries and concatenated parts are held in an [`Arc`]; clones
+/// share them.
+#[derive(Clone)]
 pub enum Transformation {
     None,
     Dct1dInverse,
     Dct1d,
     Fourier1dInverse,
     Fourier1d,
+    /// orthonormal Haar wavelet synthesis, coefficients ordered from coarse to fine
+    Haar1dInverse,
+    Haar1d,
+    /// orthonormal 2D Haar wavelet synthesis of a square image flattened row by row
+    Haar2dInverse,
+    Haar2d,
+    /// overcomplete DCT with the given redundancy, i.e. that many times more atoms than
+    /// samples, each of unit norm
+    RedundantDct1dInverse(usize),
+    /// horizontal concatenation of synthesis matrices, e.g. DCT and identity for signals made
+    /// of tones and spikes
+    Concatenated(Arc<[Transformation]>),
+    /// Gabor frame of windowed complex exponentials, sparse for signals localized in time and
+    /// frequency
+    Gabor(GaborFrame),
+    /// synthesis dictionary with one atom per column, see [`Transformation::learned`]
+    Learned(Arc<Matrix>),
 }
 
 impl Transformation {
+    /// Synthesis dictionary with one atom per column, e.g. trained with
+    /// [`DictionaryLearning`](crate::dictionary_learning::DictionaryLearning).
+    pub fn learned(dictionary: Matrix) -> Transformation {
+        Transformation::Learned(Arc::new(dictionary))
+    }
+
+    /// Synthesis matrix for signals of length `dimension`, with one column per coefficient.
+    /// Overcomplete dictionaries have more columns than rows.
     pub fn into_matrix(self, dimension: usize) -> Matrix {
         match self {
             Transformation::None => Matrix::Identity(Dimension {
@@ -26,14 +58,34 @@ impl Transformation {
             Transformation::Dct1d => Transformation::dct1d(dimension).into(),
             Transformation::Fourier1dInverse => Transformation::fft1d_inverse(dimension).into(),
             Transformation::Fourier1d => Transformation::fft1d(dimension).into(),
+            Transformation::Haar1dInverse => Transformation::haar1d(dimension).transpose().into(),
+            Transformation::Haar1d => Transformation::haar1d(dimension).into(),
+            Transformation::Haar2dInverse => Transformation::haar2d(dimension).transpose().into(),
+            Transformation::Haar2d => Transformation::haar2d(dimension).into(),
+            Transformation::RedundantDct1dInverse(redundancy) => {
+                Transformation::redundant_dct1d(dimension, redundancy).into()
+            }
+            Transformation::Concatenated(parts) => concatenate(
+                parts
+                    .iter()
+                    .map(|part| part.clone().into_matrix(dimension))
+                    .collect(),
+            ),
+            Transformation::Gabor(frame) => frame.synthesis_matrix(dimension).into(),
+            Transformation::Learned(matrix) => {
+                assert_eq!(
+                    matrix.dimension().nrows,
+                    dimension,
+                    "dictionary atoms must have the signal length"
+                );
+                matrix.as_ref().clone()
+            }
         }
     }
 
     // DCT 2, 1D
     fn dct1d(dimension: usize) -> RealMatrix {
-        // TODO reuse planner
-        let mut planner = DctPlanner::<f64>::new();
-        let dct = planner.plan_dct2(dimension);
+        let dct = planner::shared().dct2(dimension);
         let mut scratch = vec![0.0; dct.get_scratch_len()];
 
         let mut matrix = DMatrix::<f64>::identity(dimension, dimension);
@@ -43,18 +95,13 @@ impl Transformation {
 
         // normalize
         // TODO checkout Nmatrix unit
-        matrix = matrix.unscale(f64::sqrt(dimension as f64 / 2.0));
-
-        matrix.into()
+        matrix.unscale(f64::sqrt(dimension as f64 / 2.0))
     }
     // DCT 2 inverse, 1D
     // TODO consolidate methose
     fn dct1d_inverse(dimension: usize) -> RealMatrix {
-        // TODO reuse planner
-        let mut planner = DctPlanner::<f64>::new();
-
         // Inverse of DCT 2 is DCT3
-        let dct = planner.plan_dct3(dimension);
+        let dct = planner::shared().dct3(dimension);
         let mut scratch = vec![0.0; dct.get_scratch_len()];
 
         let mut matrix = DMatrix::<f64>::identity(dimension, dimension);
@@ -63,26 +110,34 @@ impl Transformation {
         }
 
         // normalize
-        matrix = matrix.unscale(f64::sqrt(dimension as f64 / 2.0));
+        matrix.unscale(f64::sqrt(dimension as f64 / 2.0))
+    }
 
-        matrix.into()
+    // atoms cos(π(2i+1)k/2p) for p = redundancy·n frequencies, normalized
+    fn redundant_dct1d(dimension: usize, redundancy: usize) -> RealMatrix {
+        assert!(redundancy > 0, "redundancy must be positive");
+        let atoms = redundancy * dimension;
+        let mut matrix = DMatrix::<f64>::from_fn(dimension, atoms, |i, k| {
+            f64::cos(std::f64::consts::PI * (2 * i + 1) as f64 * k as f64 / (2 * atoms) as f64)
+        });
+        for mut col in matrix.column_iter_mut() {
+            col.normalize_mut();
+        }
+        matrix
     }
 
     fn fft1d(dimension: usize) -> ComplexMatrix {
         Transformation::fft(dimension, FftDirection::Forward)
     }
     fn fft1d_inverse(dimension: usize) -> ComplexMatrix {
-        let mut matrix = Transformation::fft(dimension, FftDirection::Inverse);
+        let matrix = Transformation::fft(dimension, FftDirection::Inverse);
 
         let norm = dimension as f64;
-        matrix = matrix.unscale(norm);
-
-        matrix.into()
+        matrix.unscale(norm)
     }
 
     fn fft(dimension: usize, direction: FftDirection) -> ComplexMatrix {
-        let mut planner = FftPlanner::new();
-        let fft = planner.plan_fft(dimension, direction);
+        let fft = planner::shared().fft(dimension, direction);
 
         let mut scratch = vec![Complex64::new(0.0, 0.0); fft.get_inplace_scratch_len()];
         let mut matrix = DMatrix::<Complex64>::identity(dimension, dimension);
@@ -93,24 +148,128 @@ impl Transformation {
 
         matrix
     }
+
+    // full depth Haar decomposition, dimension must be a power of two
+    fn haar1d(dimension: usize) -> RealMatrix {
+        assert!(
+            dimension.is_power_of_two(),
+            "Haar needs a power of two length"
+        );
+        let mut matrix = DMatrix::<f64>::identity(dimension, dimension);
+        for mut col in matrix.column_iter_mut() {
+            let mut len = dimension;
+            while len > 1 {
+                haar_step(col.as_mut_slice(), 0, 1, len);
+                len /= 2;
+            }
+        }
+        matrix
+    }
+
+    // non standard decomposition, alternating between rows and columns on each level
+    fn haar2d(dimension: usize) -> RealMatrix {
+        let width = (dimension as f64).sqrt().round() as usize;
+        assert!(
+            width * width == dimension && width.is_power_of_two(),
+            "Haar 2D needs a square image with a power of two width"
+        );
+        let mut matrix = DMatrix::<f64>::identity(dimension, dimension);
+        for mut col in matrix.column_iter_mut() {
+            let image = col.as_mut_slice();
+            let mut len = width;
+            while len > 1 {
+                for row in 0..len {
+                    haar_step(image, row * width, 1, len);
+                }
+                for column in 0..len {
+                    haar_step(image, column, width, len);
+                }
+                len /= 2;
+            }
+        }
+        matrix
+    }
+}
+
+// side by side, complex if any of the parts is
+fn concatenate(parts: Vec<Matrix>) -> Matrix {
+    let nrows = parts.first().map_or(0, |part| part.dimension().nrows);
+    assert!(
+        parts.iter().all(|part| part.dimension().nrows == nrows),
+        "concatenated transformations must have the same number of rows"
+    );
+    let real: Option<Vec<RealMatrix>> = parts
+        .iter()
+        .map(|part| match part {
+            Matrix::Identity(dim) => Some(DMatrix::identity(dim.nrows, dim.ncols)),
+            Matrix::Real(m) => Some(m.clone()),
+            Matrix::Complex(_) => None,
+        })
+        .collect();
+    match real {
+        Some(real) => hstack(&real).into(),
+        None => {
+            let complex: Vec<ComplexMatrix> = parts
+                .into_iter()
+                .map(|part| match part {
+                    Matrix::Identity(dim) => DMatrix::identity(dim.nrows, dim.ncols),
+                    Matrix::Real(m) => m.to_superset(),
+                    Matrix::Complex(m) => m,
+                })
+                .collect();
+            hstack(&complex).into()
+        }
+    }
+}
+
+fn hstack<P>(parts: &[DMatrix<P>]) -> DMatrix<P>
+where
+    P: Precision,
+{
+    let nrows = parts.first().map_or(0, |part| part.nrows());
+    let ncols = parts.iter().map(|part| part.ncols()).sum();
+    let mut matrix = DMatrix::zeros(nrows, ncols);
+    let mut offset = 0;
+    for part in parts {
+        matrix.columns_mut(offset, part.ncols()).copy_from(part);
+        offset += part.ncols();
+    }
+    matrix
+}
+
+// one Haar level on `len` values starting at `offset`, averages first then details
+fn haar_step(values: &mut [f64], offset: usize, stride: usize, len: usize) {
+    let half = len / 2;
+    let input: Vec<f64> = (0..len).map(|i| values[offset + i * stride]).collect();
+    for i in 0..half {
+        let (a, b) = (input[2 * i], input[2 * i + 1]);
+        values[offset + i * stride] = (a + b) * std::f64::consts::FRAC_1_SQRT_2;
+        values[offset + (half + i) * stride] = (a - b) * std::f64::consts::FRAC_1_SQRT_2;
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use std::sync::Arc;
+
     use approx::assert_relative_eq;
     use nalgebra::{DMatrix, DVector};
 
-    use crate::{matrix::MatrixComplexFields, precision::Complex64, Transformation};
+    use crate::{
+        matrix::{Matrix, MatrixComplexFields},
+        precision::Complex64,
+        Transformation,
+    };
 
     const TOLERANCE: f64 = 0.01;
     const N: usize = 4;
 
     #[test]
     fn dct1d() {
-        let t: DMatrix<f64> = Transformation::dct1d(N).into();
+        let t: DMatrix<f64> = Transformation::dct1d(N);
         println!("DCT1D {}", t);
 
-        let inv: DMatrix<f64> = Transformation::dct1d_inverse(N).into();
+        let inv: DMatrix<f64> = Transformation::dct1d_inverse(N);
         println!("DCT1D inverse {}", inv);
 
         let x = DVector::<f64>::from_fn(4, |i, _| i as f64);
@@ -126,6 +285,31 @@ mod test {
         );
     }
 
+    #[test]
+    fn haar_is_orthonormal() {
+        for t in [Transformation::haar1d(8), Transformation::haar2d(16)] {
+            assert_relative_eq!(
+                DMatrix::<f64>::identity(t.nrows(), t.ncols()),
+                t.transpose() * &t,
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn haar1d_coarse_to_fine() {
+        let t = Transformation::haar1d(4);
+        let x = DVector::<f64>::from_vec(vec![1.0, 1.0, 3.0, 3.0]);
+
+        // scaling, coarsest detail, two finest details
+        let coefficients = t * x;
+        assert_relative_eq!(
+            coefficients,
+            DVector::from_vec(vec![4.0, -2.0, 0.0, 0.0]),
+            epsilon = 1e-12
+        );
+    }
+
     #[test]
     fn fft1d() {
         let t = Transformation::fft1d(N);
@@ -154,4 +338,55 @@ mod test {
             epsilon = TOLERANCE
         );
     }
+
+    #[test]
+    fn redundant_dct_has_unit_norm_atoms() {
+        let t = Transformation::redundant_dct1d(8, 3);
+        assert_eq!(t.shape(), (8, 24));
+        for atom in t.column_iter() {
+            assert_relative_eq!(atom.norm(), 1.0, epsilon = 1e-12);
+        }
+
+        // without redundancy the atoms are the orthonormal DCT basis
+        let t = Transformation::redundant_dct1d(8, 1);
+        assert_relative_eq!(
+            DMatrix::<f64>::identity(8, 8),
+            t.transpose() * &t,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn concatenation_places_parts_side_by_side() {
+        let t = Transformation::Concatenated(Arc::from([
+            Transformation::Dct1dInverse,
+            Transformation::None,
+        ]))
+        .into_matrix(N);
+        let Matrix::Real(t) = t else {
+            panic!("expected a real matrix");
+        };
+        assert_eq!(t.shape(), (N, 2 * N));
+        assert_eq!(t.columns(0, N), Transformation::dct1d_inverse(N));
+        assert_eq!(t.columns(N, N), DMatrix::<f64>::identity(N, N));
+
+        let t = Transformation::Concatenated(Arc::from([
+            Transformation::None,
+            Transformation::Fourier1dInverse,
+        ]))
+        .into_matrix(N);
+        assert!(matches!(t, Matrix::Complex(m) if m.shape() == (N, 2 * N)));
+
+        // parts chosen at runtime, e.g. a learned dictionary next to the DCT
+        let dictionary = DMatrix::<f64>::from_fn(N, 3, |i, j| (i * j) as f64);
+        let parts = vec![
+            Transformation::learned(dictionary.clone().into()),
+            Transformation::Dct1dInverse,
+        ];
+        let Matrix::Real(t) = Transformation::Concatenated(parts.into()).into_matrix(N) else {
+            panic!("expected a real matrix");
+        };
+        assert_eq!(t.columns(0, 3), dictionary);
+        assert_eq!(t.columns(3, N), Transformation::dct1d_inverse(N));
+    }
 }
@@ -0,0 +1,52 @@
+use rand::{rngs::StdRng, SeedableRng};
+use sense_motive::{
+    algorithm::{Algorithm, ApproximateMessagePassingSolver, Denoiser},
+    measurement_matrix::MeasurementMatrix,
+    signal_utils::{generate_exact_sparse_signal, relative_error_l2, Amplitude},
+    ModelBuilder, Transformation,
+};
+
+const N: usize = 512; // original length
+const M: usize = 256; // compressed length
+const K: usize = 25; // sparsity
+const SEED: u64 = 42;
+
+const TOL_ERR: f64 = 1e-3;
+
+#[test]
+fn gaussian_sensing() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let model = ModelBuilder::new()
+        .with_transformation(Transformation::None)
+        .with_measurement_matrix(MeasurementMatrix::Gaussian)
+        .with_algorithm(Algorithm::ApproximateMessagePassing(
+            ApproximateMessagePassingSolver::with_parameters(
+                Denoiser::SoftThreshold { alpha: 1.5 },
+                200,
+                1e-9,
+            ),
+        ))
+        .with_seed(SEED)
+        .build(M, N);
+
+    let original = generate_exact_sparse_signal(N, K, Amplitude::Gaussian, &mut rng);
+    let compressed = model.compress(&original);
+    let decompressed = model.decompress(&compressed);
+
+    assert!(relative_error_l2(&original, &decompressed) < TOL_ERR);
+}
+
+#[test]
+#[should_panic(expected = "approximate message passing needs a real transformation")]
+fn complex_transformation() {
+    ModelBuilder::new()
+        .with_transformation(Transformation::Fourier1dInverse)
+        .with_algorithm(Algorithm::ApproximateMessagePassing(
+            ApproximateMessagePassingSolver::with_parameters(
+                Denoiser::SoftThreshold { alpha: 1.5 },
+                200,
+                1e-9,
+            ),
+        ))
+        .build(M, N);
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "rayon")]
+
+use rand::{rngs::StdRng, SeedableRng};
+use sense_motive::{
+    signal_utils::{generate_sparse_in_basis, Amplitude},
+    ModelBuilder, Transformation,
+};
+
+const N: usize = 256; // original length
+const M: usize = 96; // compressed length
+const K: usize = 8; // sparsity
+const FRAMES: usize = 64;
+const SEED: u64 = 42;
+
+#[test]
+fn matches_sequential_decompression() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    for transformation in [Transformation::None, Transformation::Fourier1dInverse] {
+        let model = ModelBuilder::new()
+            .with_transformation(transformation.clone())
+            .with_seed(SEED)
+            .build(M, N);
+
+        let frames: Vec<Vec<f64>> = (0..FRAMES)
+            .map(|_| {
+                let original = generate_sparse_in_basis(
+                    N,
+                    K,
+                    transformation.clone(),
+                    Amplitude::Gaussian,
+                    &mut rng,
+                );
+                model.compress(&original)
+            })
+            .collect();
+
+        let batch = model.decompress_batch(&frames);
+
+        assert_eq!(batch.len(), FRAMES);
+        for (frame, decompressed) in frames.iter().zip(batch.iter()) {
+            assert_eq!(&model.decompress(frame), decompressed);
+        }
+    }
+}
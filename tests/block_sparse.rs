@@ -0,0 +1,52 @@
+use rand::{rngs::StdRng, SeedableRng};
+use sense_motive::{
+    algorithm::{
+        Algorithm, BlockOrthogonalMatchingPursuitSolver, BlockPartition, GroupLassoSolver,
+    },
+    signal_utils::{generate_block_sparse_signal, relative_error_l2, Amplitude},
+    ModelBuilder, Transformation,
+};
+
+const N: usize = 128; // original length
+const M: usize = 64; // compressed length
+const BLOCK_LEN: usize = 8;
+const BLOCKS: usize = 3; // active blocks
+const SEED: u64 = 42;
+
+fn reconstruction_error(algorithm: Algorithm) -> f64 {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let model = ModelBuilder::new()
+        .with_transformation(Transformation::None)
+        .with_algorithm(algorithm)
+        .with_seed(SEED)
+        .build(M, N);
+
+    let original = generate_block_sparse_signal(N, BLOCK_LEN, BLOCKS, Amplitude::Uniform, &mut rng);
+    let compressed = model.compress(&original);
+    let decompressed = model.decompress(&compressed);
+
+    relative_error_l2(&original, &decompressed)
+}
+
+#[test]
+fn block_omp() {
+    let algorithm = Algorithm::BlockOrthogonalMatchingPursuit(
+        BlockOrthogonalMatchingPursuitSolver::with_parameters(
+            BlockPartition::uniform(N, BLOCK_LEN),
+            BLOCKS,
+            1e-9,
+        ),
+    );
+    assert!(reconstruction_error(algorithm) < 1e-9);
+}
+
+#[test]
+fn group_lasso() {
+    let algorithm = Algorithm::GroupLasso(GroupLassoSolver::with_parameters(
+        BlockPartition::uniform(N, BLOCK_LEN),
+        1e-4,
+        10000,
+        1e-10,
+    ));
+    assert!(reconstruction_error(algorithm) < 1e-3);
+}
@@ -0,0 +1,69 @@
+use nalgebra::{DMatrix, DVector};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::StandardNormal;
+use sense_motive::{
+    algorithm::{Algorithm, OrthogonalMatchingPursuitSolver},
+    dictionary_learning::{DictionaryLearning, DictionaryUpdate},
+    signal_utils::relative_error_l2,
+    ModelBuilder, Transformation,
+};
+
+const N: usize = 32; // original length
+const M: usize = 16; // compressed length
+const K: usize = 2; // sparsity
+const ATOMS: usize = 48;
+const TRAINING_FRAMES: usize = 300;
+const TEST_FRAMES: usize = 10;
+const SEED: u64 = 42;
+
+// frames which are sparse in a hidden overcomplete dictionary, but not in any fixed basis
+fn generate_frames(dictionary: &DMatrix<f64>, frames: usize, rng: &mut StdRng) -> Vec<Vec<f64>> {
+    (0..frames)
+        .map(|_| {
+            let mut frame = DVector::zeros(N);
+            for _ in 0..K {
+                let atom = rng.gen_range(0..ATOMS);
+                let amplitude = 1.0 + rng.gen::<f64>();
+                frame.axpy(amplitude, &dictionary.column(atom), 1.0);
+            }
+            frame.data.into()
+        })
+        .collect()
+}
+
+fn mean_error(transformation: Transformation, frames: &[Vec<f64>]) -> f64 {
+    let model = ModelBuilder::new()
+        .with_transformation(transformation)
+        .with_algorithm(Algorithm::OrthogonalMatchingPursuit(
+            OrthogonalMatchingPursuitSolver::with_parameters(K, 1e-9),
+        ))
+        .with_seed(SEED)
+        .build(M, N);
+
+    let total: f64 = frames
+        .iter()
+        .map(|frame| relative_error_l2(frame, &model.decompress(model.compress(frame))))
+        .sum();
+    total / frames.len() as f64
+}
+
+#[test]
+fn learned_dictionary_beats_dct() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let hidden = DMatrix::from_fn(N, ATOMS, |_, _| rng.sample::<f64, _>(StandardNormal));
+    let training = generate_frames(&hidden, TRAINING_FRAMES, &mut rng);
+    let test = generate_frames(&hidden, TEST_FRAMES, &mut rng);
+
+    let learned = DictionaryLearning::new(ATOMS, K)
+        .with_update(DictionaryUpdate::KSvd)
+        .with_iterations(10)
+        .with_seed(SEED)
+        .train(&training);
+
+    let learned_error = mean_error(Transformation::learned(learned.into()), &test);
+    let dct_error = mean_error(Transformation::Dct1dInverse, &test);
+    assert!(
+        learned_error < 0.5 * dct_error && learned_error < 0.4,
+        "learned dictionary error {learned_error}, DCT error {dct_error}"
+    );
+}
@@ -0,0 +1,94 @@
+use rand::{rngs::StdRng, SeedableRng};
+use sense_motive::{
+    entropy_coding::{bits_per_sample, DecodeError},
+    quantization::{QuantizationRange, Quantizer},
+    signal_utils::{generate_exact_sparse_signal, Amplitude},
+    Model, ModelBuilder, Transformation,
+};
+
+const BITS: u8 = 4;
+const SEED: u64 = 42;
+
+fn model(size_compressed: usize, size_original: usize) -> Model {
+    model_with_quantizer(
+        size_compressed,
+        size_original,
+        Quantizer::Uniform {
+            bits: BITS,
+            range: QuantizationRange::Adaptive,
+        },
+    )
+}
+
+fn model_with_quantizer(
+    size_compressed: usize,
+    size_original: usize,
+    quantizer: Quantizer,
+) -> Model {
+    ModelBuilder::new()
+        .with_transformation(Transformation::None)
+        .with_quantizer(quantizer)
+        .with_seed(SEED)
+        .build(size_compressed, size_original)
+}
+
+#[test]
+fn encoded_roundtrip_matches_quantized() {
+    const N: usize = 128;
+    let model = model(64, N);
+    let original =
+        generate_exact_sparse_signal(N, 4, Amplitude::Gaussian, &mut StdRng::seed_from_u64(SEED));
+
+    let encoded = model.compress_encoded(&original);
+    let decompressed = model.decompress_encoded(&encoded).unwrap();
+
+    assert_eq!(
+        decompressed,
+        model.decompress_quantized(&model.compress_quantized(&original))
+    );
+}
+
+#[test]
+fn entropy_coding_beats_bit_packing() {
+    const N: usize = 2048;
+    const M: usize = 1024;
+    let model = model(M, N);
+    // dense signal, so the measurements are approximately gaussian
+    let original =
+        generate_exact_sparse_signal(N, N, Amplitude::Gaussian, &mut StdRng::seed_from_u64(SEED));
+
+    let encoded = model.compress_encoded(&original);
+
+    let packed = (M * BITS as usize) as f64 / N as f64;
+    assert!(bits_per_sample(&encoded, N) < packed);
+}
+
+#[test]
+fn rejects_streams_of_other_models() {
+    const N: usize = 128;
+    let original =
+        generate_exact_sparse_signal(N, 4, Amplitude::Gaussian, &mut StdRng::seed_from_u64(SEED));
+    let encoded = model(64, N).compress_encoded(&original);
+
+    assert_eq!(
+        model(32, N).decompress_encoded(&encoded),
+        Err(DecodeError::TooManyMeasurements(64, 32))
+    );
+    assert_eq!(
+        model(96, N).decompress_encoded(&encoded),
+        Err(DecodeError::MeasurementCount(64, 96))
+    );
+    let mu_law = model_with_quantizer(
+        64,
+        N,
+        Quantizer::MuLaw {
+            bits: BITS,
+            range: QuantizationRange::Adaptive,
+            mu: 255.0,
+        },
+    );
+    assert_eq!(
+        mu_law.decompress_encoded(&encoded),
+        Err(DecodeError::QuantizerMismatch)
+    );
+}
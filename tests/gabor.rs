@@ -0,0 +1,56 @@
+use rand::{rngs::StdRng, SeedableRng};
+use sense_motive::{
+    algorithm::{Algorithm, OrthogonalMatchingPursuitSolver},
+    gabor::{GaborFrame, Window},
+    signal_utils::{generate_sparse_in_basis, relative_error_l2, Amplitude},
+    Coefficients, Model, ModelBuilder, Transformation,
+};
+
+const N: usize = 128; // original length
+const M: usize = 48; // compressed length
+const K: usize = 4; // sparsity
+const SEED: u64 = 42;
+
+const TOL_ERR: f64 = 1e-6;
+
+fn build_model(transformation: Transformation) -> Model {
+    ModelBuilder::new()
+        .with_transformation(transformation)
+        .with_algorithm(Algorithm::OrthogonalMatchingPursuit(
+            OrthogonalMatchingPursuitSolver::with_parameters(M / 2, 1e-9),
+        ))
+        .with_seed(SEED)
+        .build(M, N)
+}
+
+#[test]
+fn time_frequency_sparse_signal() {
+    // half overlapping windows, rectangular ones keep the bins of a frame incoherent
+    let frame = GaborFrame::with_parameters(32, 16)
+        .with_window(Window::Rectangular)
+        .with_fft_size(32);
+    let mut rng = StdRng::seed_from_u64(SEED);
+    // real part of a few atoms, i.e. each atom along with its conjugate
+    let original = generate_sparse_in_basis(
+        N,
+        K,
+        Transformation::Gabor(frame),
+        Amplitude::Gaussian,
+        &mut rng,
+    );
+
+    let model = build_model(Transformation::Gabor(frame));
+    assert_eq!(model.coefficient_len(), frame.coefficient_len(N));
+
+    let compressed = model.compress(&original);
+    let coefficients = model.decompress_coefficients(&compressed);
+    assert!(matches!(&coefficients, Coefficients::Complex(c) if c.len() == 8 * 32));
+
+    let decompressed = model.decompress(&compressed);
+    assert!(relative_error_l2(&original, &decompressed) < TOL_ERR);
+
+    // localized tones are not sparse in the global Fourier basis
+    let fourier = build_model(Transformation::Fourier1dInverse);
+    let error = relative_error_l2(&original, &fourier.decompress(fourier.compress(&original)));
+    assert!(error > 0.1);
+}
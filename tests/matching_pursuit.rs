@@ -1,6 +1,10 @@
 use approx::assert_relative_eq;
+use rand::{rngs::StdRng, SeedableRng};
 use sense_motive::{
-    signal_utils::{diff, error_l2, error_l2_norm, generate_cos_signal, generate_sparse_signal},
+    signal_utils::{
+        diff, error_l2, error_l2_norm, generate_cos_signal_with_rng,
+        generate_sparse_signal_with_rng,
+    },
     ModelBuilder, Transformation,
 };
 
@@ -9,8 +13,7 @@ const M: usize = 64; // compressed length
 const K: usize = 4; // sparsity
 
 const TOL_ERR: f64 = 0.1;
-// TODO add setting the seed to the API
-// const SEED: u64 = 42;
+const SEED: u64 = 42;
 
 #[test]
 fn reconstruct_with_bernoulli() {
@@ -20,9 +23,10 @@ fn reconstruct_with_bernoulli() {
         //     tolerance: 0.1,
         // }))
         .with_transformation(Transformation::None)
+        .with_seed(SEED)
         .build(M, N);
 
-    let original = generate_sparse_signal(N, K);
+    let original = generate_sparse_signal_with_rng(N, K, &mut StdRng::seed_from_u64(SEED));
 
     let compressed = model.compress(&original);
     let decompressed = model.decompress(&compressed);
@@ -39,9 +43,10 @@ fn reconstruct_with_bernoulli_and_dct() {
         //     tolerance: 0.1,
         // }))
         .with_transformation(Transformation::Dct1dInverse)
+        .with_seed(SEED)
         .build(M, N);
 
-    let original = generate_cos_signal(N, K);
+    let original = generate_cos_signal_with_rng(N, K, &mut StdRng::seed_from_u64(SEED));
 
     let compressed = model.compress(&original);
     let decompressed = model.decompress(&compressed);
@@ -0,0 +1,77 @@
+use rand::{rngs::StdRng, seq::index, Rng, SeedableRng};
+use sense_motive::{
+    algorithm::{
+        MFocussSolver, MultipleMeasurementAlgorithm, SimultaneousOrthogonalMatchingPursuitSolver,
+    },
+    signal_utils::relative_error_l2,
+    ModelBuilder, Transformation,
+};
+
+const N: usize = 128; // original length
+const M: usize = 32; // compressed length
+const K: usize = 10; // sparsity
+const CHANNELS: usize = 8;
+const SEED: u64 = 42;
+
+const TOL_ERR: f64 = 1e-3;
+
+// channels sharing the same support with different amplitudes
+fn generate_channels() -> Vec<Vec<f64>> {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let support = index::sample(&mut rng, N, K).into_vec();
+    (0..CHANNELS)
+        .map(|_| {
+            let mut channel = vec![0.0; N];
+            for idx in &support {
+                channel[*idx] = rng.gen_range(-1.0..1.0);
+            }
+            channel
+        })
+        .collect()
+}
+
+fn assert_reconstructs(algorithm: MultipleMeasurementAlgorithm) {
+    let model = ModelBuilder::new()
+        .with_transformation(Transformation::None)
+        .with_multiple_measurement_algorithm(algorithm)
+        .with_seed(SEED)
+        .build(M, N);
+
+    let channels = generate_channels();
+    let compressed = model.compress_channels(&channels);
+    let decompressed = model.decompress_channels(&compressed);
+
+    assert_eq!(decompressed.len(), CHANNELS);
+    for (original, decompressed) in channels.iter().zip(decompressed.iter()) {
+        assert!(relative_error_l2(original, decompressed) < TOL_ERR);
+    }
+}
+
+#[test]
+fn reconstruct_channels_with_somp() {
+    assert_reconstructs(
+        MultipleMeasurementAlgorithm::SimultaneousOrthogonalMatchingPursuit(
+            SimultaneousOrthogonalMatchingPursuitSolver::with_parameters(M, 1e-9),
+        ),
+    );
+}
+
+#[test]
+fn reconstruct_channels_with_m_focuss() {
+    assert_reconstructs(MultipleMeasurementAlgorithm::MFocuss(
+        MFocussSolver::with_parameters(500, 1e-12, 0.8, 1e-12),
+    ));
+}
+
+#[test]
+fn no_channels() {
+    let model = ModelBuilder::new().with_seed(SEED).build(M, N);
+    assert!(model.decompress_channels::<Vec<f64>>(&[]).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "channel 1 has 31 measurements, the model expects 32")]
+fn ragged_channels() {
+    let model = ModelBuilder::new().with_seed(SEED).build(M, N);
+    model.decompress_channels(&[vec![0.0; M], vec![0.0; M - 1]]);
+}
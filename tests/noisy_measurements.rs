@@ -0,0 +1,65 @@
+use rand::{rngs::StdRng, SeedableRng};
+use sense_motive::{
+    algorithm::{Algorithm, OrthogonalMatchingPursuitSolver},
+    noise::NoiseModel,
+    signal_utils::{generate_exact_sparse_signal, relative_error_l2, support, Amplitude},
+    ModelBuilder, Transformation,
+};
+
+const N: usize = 128; // original length
+const M: usize = 64; // compressed length
+const K: usize = 4; // sparsity
+const SIGMA: f64 = 0.01;
+const SEED: u64 = 42;
+
+const TOL_ERR: f64 = 0.1;
+
+// tolerance far below the noise level, so the solver would fit the noise
+fn overfitting_algorithm() -> Algorithm {
+    Algorithm::OrthogonalMatchingPursuit(OrthogonalMatchingPursuitSolver::with_parameters(N, 1e-9))
+}
+
+fn reconstruct(noise: NoiseModel) -> (Vec<f64>, Vec<f64>) {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let original = generate_exact_sparse_signal(N, K, Amplitude::Rademacher, &mut rng);
+
+    let measuring = ModelBuilder::new()
+        .with_transformation(Transformation::None)
+        .with_noise(NoiseModel::Gaussian { sigma: SIGMA })
+        .with_seed(SEED)
+        .build(M, N);
+    let compressed = measuring.compress_with_noise(&original, &mut rng);
+
+    let model = ModelBuilder::new()
+        .with_transformation(Transformation::None)
+        .with_algorithm(overfitting_algorithm())
+        .with_noise(noise)
+        .with_seed(SEED)
+        .build(M, N);
+    let decompressed = model.decompress(&compressed);
+
+    (original, decompressed)
+}
+
+#[test]
+fn known_noise_stops_at_discrepancy() {
+    let (original, decompressed) = reconstruct(NoiseModel::Gaussian { sigma: SIGMA });
+
+    assert!(relative_error_l2(&original, &decompressed) < TOL_ERR);
+    assert_eq!(support(&decompressed, 0.0).len(), K);
+}
+
+#[test]
+fn estimated_noise_stops_at_discrepancy() {
+    let (original, decompressed) = reconstruct(NoiseModel::Estimated);
+
+    assert!(relative_error_l2(&original, &decompressed) < TOL_ERR);
+    assert!(support(&decompressed, 0.0).len() < M / 2);
+}
+
+#[test]
+fn without_noise_model_the_noise_is_fitted() {
+    let (_, decompressed) = reconstruct(NoiseModel::None);
+
+    assert!(support(&decompressed, 0.0).len() > 2 * K);
+}
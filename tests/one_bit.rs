@@ -0,0 +1,39 @@
+use rand::{rngs::StdRng, SeedableRng};
+use sense_motive::{
+    algorithm::BinaryIterativeHardThresholdingSolver,
+    measurement_matrix::MeasurementMatrix,
+    signal_utils::{generate_exact_sparse_signal, relative_error_l2, Amplitude},
+    ModelBuilder, Transformation,
+};
+
+const N: usize = 64; // original length
+const M: usize = 512; // number of sign measurements
+const K: usize = 3; // sparsity
+const SEED: u64 = 42;
+
+const TOL_ERR: f64 = 0.2;
+
+#[test]
+fn reconstruct_from_signs_with_norm() {
+    let model = ModelBuilder::new()
+        .with_transformation(Transformation::None)
+        .with_measurement_matrix(MeasurementMatrix::Gaussian)
+        .with_one_bit(BinaryIterativeHardThresholdingSolver::with_parameters(
+            K, 100,
+        ))
+        .with_seed(SEED)
+        .build(M, N);
+
+    let original =
+        generate_exact_sparse_signal(N, K, Amplitude::Gaussian, &mut StdRng::seed_from_u64(SEED));
+
+    let compressed = model.compress_one_bit(&original);
+    assert_eq!(compressed.as_bytes().len(), M / 8);
+
+    let decompressed = model.decompress_one_bit(&compressed);
+    assert!(relative_error_l2(&original, &decompressed) < TOL_ERR);
+
+    let direction = model.decompress_one_bit(&compressed.without_norm());
+    let norm: f64 = direction.iter().map(|e| e * e).sum::<f64>().sqrt();
+    approx::assert_relative_eq!(norm, 1.0, epsilon = 1e-9);
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use rand::{rngs::StdRng, SeedableRng};
+use sense_motive::{
+    algorithm::{Algorithm, OrthogonalMatchingPursuitSolver},
+    signal_utils::{generate_sparse_in_basis, relative_error_l2, Amplitude},
+    Coefficients, Model, ModelBuilder, Transformation,
+};
+
+const N: usize = 128; // original length
+const M: usize = 64; // compressed length
+const K: usize = 8; // sparsity
+const SEED: u64 = 42;
+
+const TOL_ERR: f64 = 1e-6;
+
+fn spikes_and_tones() -> Transformation {
+    Transformation::Concatenated(Arc::from([
+        Transformation::Dct1dInverse,
+        Transformation::None,
+    ]))
+}
+
+fn build_model(transformation: Transformation) -> Model {
+    ModelBuilder::new()
+        .with_transformation(transformation)
+        .with_algorithm(Algorithm::OrthogonalMatchingPursuit(
+            OrthogonalMatchingPursuitSolver::with_parameters(M, 1e-9),
+        ))
+        .with_seed(SEED)
+        .build(M, N)
+}
+
+#[test]
+fn spikes_and_tones_dictionary() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let original =
+        generate_sparse_in_basis(N, K, spikes_and_tones(), Amplitude::Gaussian, &mut rng);
+    assert_eq!(original.len(), N);
+
+    let model = build_model(spikes_and_tones());
+    assert_eq!(model.signal_len(), N);
+    assert_eq!(model.coefficient_len(), 2 * N);
+
+    let compressed = model.compress(&original);
+    let coefficients = model.decompress_coefficients(&compressed);
+    assert!(matches!(&coefficients, Coefficients::Real(c) if c.len() == 2 * N));
+
+    let decompressed = model.decompress(&compressed);
+    assert_eq!(decompressed, model.synthesize(&coefficients));
+    assert!(relative_error_l2(&original, &decompressed) < TOL_ERR);
+
+    // neither part alone represents the signal sparsely
+    let dct_only = build_model(Transformation::Dct1dInverse);
+    let error = relative_error_l2(
+        &original,
+        &dct_only.decompress(dct_only.compress(&original)),
+    );
+    assert!(error > 0.1);
+}
+
+#[test]
+fn redundant_dct() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let transformation = Transformation::RedundantDct1dInverse(2);
+    let original =
+        generate_sparse_in_basis(N, 4, transformation.clone(), Amplitude::Gaussian, &mut rng);
+
+    let model = build_model(transformation);
+    assert_eq!(model.coefficient_len(), 2 * N);
+
+    let decompressed = model.decompress(model.compress(&original));
+    assert!(relative_error_l2(&original, &decompressed) < 0.01);
+}
@@ -0,0 +1,98 @@
+use rand::{rngs::StdRng, SeedableRng};
+use sense_motive::{
+    quantization::{QuantizationRange, Quantizer},
+    signal_utils::{generate_exact_sparse_signal, relative_error_l2, Amplitude},
+    ModelBuilder, Transformation,
+};
+
+const N: usize = 128; // original length
+const M: usize = 64; // compressed length
+const K: usize = 4; // sparsity
+const SEED: u64 = 42;
+
+fn reconstruct(quantizer: Quantizer) -> (Vec<f64>, Vec<f64>, usize) {
+    let model = ModelBuilder::new()
+        .with_transformation(Transformation::None)
+        .with_quantizer(quantizer)
+        .with_seed(SEED)
+        .build(M, N);
+
+    let original =
+        generate_exact_sparse_signal(N, K, Amplitude::Gaussian, &mut StdRng::seed_from_u64(SEED));
+
+    let compressed = model.compress_quantized(&original);
+    let decompressed = model.decompress_quantized(&compressed);
+
+    (original, decompressed, compressed.as_bytes().len())
+}
+
+#[test]
+fn reconstruct_with_uniform_quantization() {
+    let (original, decompressed, bytes) = reconstruct(Quantizer::Uniform {
+        bits: 8,
+        range: QuantizationRange::Adaptive,
+    });
+
+    assert_eq!(bytes, M);
+    assert!(relative_error_l2(&original, &decompressed) < 0.05);
+}
+
+#[test]
+fn reconstruct_with_mu_law_quantization() {
+    let (original, decompressed, bytes) = reconstruct(Quantizer::MuLaw {
+        bits: 6,
+        range: QuantizationRange::Adaptive,
+        mu: 16.0,
+    });
+
+    assert_eq!(bytes, M * 6 / 8);
+    assert!(relative_error_l2(&original, &decompressed) < 0.1);
+}
+
+#[test]
+fn fewer_bits_increase_the_error() {
+    let error = |bits| {
+        let (original, decompressed, _) = reconstruct(Quantizer::Uniform {
+            bits,
+            range: QuantizationRange::Adaptive,
+        });
+        relative_error_l2(&original, &decompressed)
+    };
+
+    assert!(error(3) > error(10));
+}
+
+#[test]
+#[should_panic(expected = "the model expects 64")]
+fn rejects_measurements_of_other_models() {
+    let quantizer = Quantizer::Uniform {
+        bits: 8,
+        range: QuantizationRange::Adaptive,
+    };
+    let model = ModelBuilder::new()
+        .with_transformation(Transformation::None)
+        .with_quantizer(quantizer)
+        .with_seed(SEED)
+        .build(M, N);
+
+    model.decompress_quantized(&quantizer.quantize(&[0.5; M / 2]));
+}
+
+#[test]
+#[should_panic(expected = "quantized with 4 bits")]
+fn rejects_other_bit_depths() {
+    let model = ModelBuilder::new()
+        .with_transformation(Transformation::None)
+        .with_quantizer(Quantizer::Uniform {
+            bits: 8,
+            range: QuantizationRange::Adaptive,
+        })
+        .with_seed(SEED)
+        .build(M, N);
+    let other = Quantizer::Uniform {
+        bits: 4,
+        range: QuantizationRange::Adaptive,
+    };
+
+    model.decompress_quantized(&other.quantize(&[0.5; M]));
+}
@@ -0,0 +1,67 @@
+use rand::{rngs::StdRng, SeedableRng};
+use sense_motive::{
+    algorithm::{
+        Algorithm, EpsilonSchedule, IterativelyReweightedLeastSquaresSolver, L1Solver,
+        LeastSquaresSolver, ReweightedL1Solver,
+    },
+    signal_utils::{generate_exact_sparse_signal, relative_error_l2, Amplitude},
+    ModelBuilder, Transformation,
+};
+
+const N: usize = 128; // original length
+const M: usize = 40; // compressed length
+const K: usize = 15; // sparsity, at the edge of l1 recovery for these dimensions
+const SEED: u64 = 42;
+
+const TOL_ERR: f64 = 1e-4;
+
+fn reconstruction_error(algorithm: Algorithm) -> f64 {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let model = ModelBuilder::new()
+        .with_transformation(Transformation::None)
+        .with_algorithm(algorithm)
+        .with_seed(SEED)
+        .build(M, N);
+
+    let original = generate_exact_sparse_signal(N, K, Amplitude::Gaussian, &mut rng);
+    let compressed = model.compress(&original);
+    let decompressed = model.decompress(&compressed);
+    relative_error_l2(&original, &decompressed)
+}
+
+fn reweighted_l1(outer_iter: usize) -> Algorithm {
+    Algorithm::ReweightedL1(ReweightedL1Solver::with_parameters(
+        outer_iter,
+        EpsilonSchedule::Fixed(0.1),
+        L1Solver::Admm {
+            rho: 1.0,
+            max_iter: 5000,
+        },
+    ))
+}
+
+#[test]
+fn reweighting_improves_on_l1() {
+    // a single outer iteration with unit weights is plain basis pursuit
+    let l1 = reconstruction_error(reweighted_l1(1));
+    let reweighted = reconstruction_error(reweighted_l1(10));
+
+    assert!(l1 > TOL_ERR);
+    assert!(reweighted < TOL_ERR);
+}
+
+#[test]
+fn irls() {
+    let algorithm = Algorithm::IterativelyReweightedLeastSquares(
+        IterativelyReweightedLeastSquaresSolver::with_parameters(
+            0.5,
+            500,
+            EpsilonSchedule::Adaptive {
+                initial: 1.0,
+                min: 1e-12,
+            },
+            LeastSquaresSolver::Cholesky,
+        ),
+    );
+    assert!(reconstruction_error(algorithm) < TOL_ERR);
+}
@@ -0,0 +1,123 @@
+use rand::{rngs::StdRng, SeedableRng};
+use sense_motive::{
+    algorithm::{Algorithm, KalmanFilteredCsSolver, OrthogonalMatchingPursuitSolver, WarmStart},
+    noise::NoiseModel,
+    signal_utils::{generate_sparse_sequence, relative_error_l2, support, Amplitude},
+    Coefficients, ModelBuilder, Transformation,
+};
+
+const N: usize = 256; // original length
+const M: usize = 64; // compressed length
+const K: usize = 20; // sparsity
+const FRAMES: usize = 30;
+const SEED: u64 = 42;
+
+const TOL_ERR: f64 = 1e-6;
+
+#[test]
+fn warm_start_with_previous_support() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let model = ModelBuilder::new()
+        .with_transformation(Transformation::None)
+        .with_algorithm(Algorithm::OrthogonalMatchingPursuit(
+            OrthogonalMatchingPursuitSolver::with_parameters(M, 1e-9),
+        ))
+        .with_seed(SEED)
+        .build(M, N);
+    let sequence = generate_sparse_sequence(N, K, FRAMES, 1, 0.05, Amplitude::Gaussian, &mut rng);
+
+    let mut decoder = model.sequential_decoder();
+    let mut cold_failures = 0;
+    for original in sequence.iter() {
+        let compressed = model.compress(original);
+
+        let warm = decoder.decode(&compressed);
+        assert!(relative_error_l2(original, &warm) < TOL_ERR);
+        assert_eq!(decoder.support().unwrap(), support(original, 0.0));
+
+        if relative_error_l2(original, &model.decompress(&compressed)) > TOL_ERR {
+            cold_failures += 1;
+        }
+    }
+    // close to the phase transition, OMP from scratch fails on some frames
+    assert!(cold_failures > 0);
+
+    decoder.reset();
+    assert!(decoder.support().is_none());
+}
+
+#[test]
+fn warm_start_without_headroom() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let model = ModelBuilder::new()
+        .with_transformation(Transformation::None)
+        .with_algorithm(Algorithm::OrthogonalMatchingPursuit(
+            OrthogonalMatchingPursuitSolver::with_parameters(K, 1e-9),
+        ))
+        .with_seed(SEED)
+        .build(M, N);
+    let sequence = generate_sparse_sequence(N, K, FRAMES, 2, 0.05, Amplitude::Gaussian, &mut rng);
+
+    // the stale atoms of the previous support are pruned, the moved ones fit into max_iter
+    let mut decoder = model.sequential_decoder();
+    for original in sequence.iter() {
+        let warm = decoder.decode(model.compress(original));
+        assert!(relative_error_l2(original, &warm) < TOL_ERR);
+    }
+}
+
+#[test]
+fn warm_start_with_previous_solution() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let model = ModelBuilder::new()
+        .with_transformation(Transformation::None)
+        .with_algorithm(Algorithm::OrthogonalMatchingPursuit(
+            OrthogonalMatchingPursuitSolver::with_parameters(K, 1e-9),
+        ))
+        .with_seed(SEED)
+        .build(M, N);
+    let sequence = generate_sparse_sequence(N, K, 2, 1, 0.05, Amplitude::Gaussian, &mut rng);
+
+    let Coefficients::Real(previous) = model.decompress_coefficients(model.compress(&sequence[0]))
+    else {
+        panic!("real coefficients");
+    };
+    let warm_start = WarmStart::Solution(previous.into());
+    let reconstruction = model.decompress_warm(model.compress(&sequence[1]), Some(&warm_start));
+    assert!(relative_error_l2(&sequence[1], &reconstruction.signal) < TOL_ERR);
+    assert_eq!(reconstruction.support, support(&sequence[1], 0.0));
+}
+
+#[test]
+fn kalman_filtered_tracking() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let sigma = 0.005;
+    let model = ModelBuilder::new()
+        .with_transformation(Transformation::None)
+        .with_noise(NoiseModel::Gaussian { sigma })
+        .with_seed(SEED)
+        .build(M, N);
+    let sequence = generate_sparse_sequence(N, 10, 15, 1, 0.02, Amplitude::Rademacher, &mut rng);
+
+    let solver = KalmanFilteredCsSolver::with_parameters(1e-3, 200, 0.3, 0.15).with_variances(
+        0.02 * 0.02,
+        sigma * sigma,
+        1.0,
+    );
+    let mut decoder = model.dynamic_decoder(solver);
+    let mut tracked_error = 0.0;
+    let mut cold_error = 0.0;
+    for original in sequence.iter() {
+        let compressed = model.compress_with_noise(original, &mut rng);
+
+        let tracked = decoder.decode(&compressed);
+        let mut tracked_support = decoder.support().to_vec();
+        tracked_support.sort_unstable();
+        assert_eq!(tracked_support, support(original, 0.0));
+
+        tracked_error += relative_error_l2(original, &tracked);
+        cold_error += relative_error_l2(original, &model.decompress(&compressed));
+    }
+    assert!(tracked_error < cold_error);
+    assert!(tracked_error / (sequence.len() as f64) < 0.02);
+}
@@ -0,0 +1,75 @@
+use rand::{rngs::StdRng, SeedableRng};
+use sense_motive::{
+    algorithm::{Algorithm, SparseBayesianLearningSolver},
+    measurement_matrix::MeasurementMatrix,
+    noise::NoiseModel,
+    signal_utils::{generate_sparse_in_basis, relative_error_l2, Amplitude},
+    ModelBuilder, Transformation, UncertainReconstruction,
+};
+
+const N: usize = 64; // original length
+const M: usize = 48; // compressed length
+const K: usize = 4; // sparsity
+const SIGMA: f64 = 0.01;
+const SEED: u64 = 42;
+
+fn reconstruct(noise: NoiseModel) -> (Vec<f64>, UncertainReconstruction) {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let original = generate_sparse_in_basis(
+        N,
+        K,
+        Transformation::Dct1dInverse,
+        Amplitude::Rademacher,
+        &mut rng,
+    );
+
+    let measuring = ModelBuilder::new()
+        .with_transformation(Transformation::Dct1dInverse)
+        .with_measurement_matrix(MeasurementMatrix::Gaussian)
+        .with_noise(NoiseModel::Gaussian { sigma: SIGMA })
+        .with_seed(SEED)
+        .build(M, N);
+    let compressed = measuring.compress_with_noise(&original, &mut rng);
+
+    let model = ModelBuilder::new()
+        .with_transformation(Transformation::Dct1dInverse)
+        .with_measurement_matrix(MeasurementMatrix::Gaussian)
+        .with_algorithm(Algorithm::SparseBayesianLearning(
+            SparseBayesianLearningSolver::with_parameters(300, 1e-6),
+        ))
+        .with_noise(noise)
+        .with_seed(SEED)
+        .build(M, N);
+    (original, model.decompress_with_uncertainty(&compressed))
+}
+
+// share of samples whose error is within three posterior standard deviations
+fn coverage(original: &[f64], reconstruction: &UncertainReconstruction) -> f64 {
+    let covered = original
+        .iter()
+        .zip(reconstruction.signal.iter())
+        .zip(reconstruction.signal_variances.iter())
+        .filter(|((o, s), v)| (*o - *s).abs() <= 3.0 * v.sqrt())
+        .count();
+    covered as f64 / original.len() as f64
+}
+
+#[test]
+fn known_noise() {
+    let (original, reconstruction) = reconstruct(NoiseModel::Gaussian { sigma: SIGMA });
+
+    assert_eq!(reconstruction.noise_variance, SIGMA * SIGMA);
+    assert!(relative_error_l2(&original, &reconstruction.signal) < 0.05);
+    // pruned coefficients have no variance, so the error bars are somewhat optimistic
+    assert!(coverage(&original, &reconstruction) > 0.8);
+}
+
+#[test]
+fn estimated_noise() {
+    let (original, reconstruction) = reconstruct(NoiseModel::Estimated);
+
+    assert!(reconstruction.noise_variance > SIGMA * SIGMA / 4.0);
+    assert!(reconstruction.noise_variance < SIGMA * SIGMA * 4.0);
+    assert!(relative_error_l2(&original, &reconstruction.signal) < 0.05);
+    assert_eq!(reconstruction.coefficient_variances.len(), N);
+}
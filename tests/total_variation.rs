@@ -0,0 +1,123 @@
+use rand::{rngs::StdRng, SeedableRng};
+use sense_motive::{
+    algorithm::{SignalShape, TotalVariationSolver},
+    noise::NoiseModel,
+    signal_utils::relative_error_l2,
+    ModelBuilder,
+};
+
+const SEED: u64 = 42;
+
+// piecewise constant with four jumps
+fn generate_steps(len: usize) -> Vec<f64> {
+    (0..len)
+        .map(|i| match i * 8 / len {
+            0 | 1 => 0.5,
+            2 => -1.0,
+            3..=5 => 2.0,
+            6 => 0.0,
+            _ => 1.0,
+        })
+        .collect()
+}
+
+// bright rectangle on a dark background
+fn generate_image(width: usize) -> Vec<f64> {
+    (0..width * width)
+        .map(|i| {
+            let (row, col) = (i / width, i % width);
+            if (4..11).contains(&row) && (3..9).contains(&col) {
+                1.0
+            } else {
+                0.2
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn signal_1d() {
+    const N: usize = 128;
+    const M: usize = 40;
+    let model = ModelBuilder::new()
+        .with_total_variation(TotalVariationSolver::with_parameters(
+            SignalShape::Signal1d,
+            20000,
+            0.0,
+        ))
+        .with_seed(SEED)
+        .build(M, N);
+
+    let original = generate_steps(N);
+    let compressed = model.compress(&original);
+    let decompressed = model.decompress_total_variation(&compressed);
+
+    assert!(relative_error_l2(&original, &decompressed) < 1e-4);
+}
+
+#[test]
+fn image() {
+    const WIDTH: usize = 16;
+    const M: usize = 128;
+    let model = ModelBuilder::new()
+        .with_total_variation(TotalVariationSolver::with_parameters(
+            SignalShape::Image { width: WIDTH },
+            20000,
+            0.0,
+        ))
+        .with_seed(SEED)
+        .build(M, WIDTH * WIDTH);
+
+    let original = generate_image(WIDTH);
+    let compressed = model.compress(&original);
+    let decompressed = model.decompress_total_variation(&compressed);
+
+    assert!(relative_error_l2(&original, &decompressed) < 1e-4);
+}
+
+#[test]
+fn noisy_signal_1d() {
+    const N: usize = 128;
+    const M: usize = 64;
+    let noise = NoiseModel::Gaussian { sigma: 0.01 };
+    let model = ModelBuilder::new()
+        .with_total_variation(TotalVariationSolver::with_parameters(
+            SignalShape::Signal1d,
+            2000,
+            0.0,
+        ))
+        .with_noise(noise)
+        .with_seed(SEED)
+        .build(M, N);
+
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let original = generate_steps(N);
+    let compressed = model.compress_with_noise(&original, &mut rng);
+    let decompressed = model.decompress_total_variation(&compressed);
+
+    assert!(relative_error_l2(&original, &decompressed) < 0.02);
+}
+
+#[test]
+#[should_panic(expected = "can't be split into image rows of width 4")]
+fn image_of_partial_rows() {
+    ModelBuilder::new()
+        .with_total_variation(TotalVariationSolver::with_parameters(
+            SignalShape::Image { width: 4 },
+            10,
+            0.0,
+        ))
+        .build(5, 10);
+}
+
+#[test]
+#[should_panic(expected = "image rows of width 0")]
+fn image_of_zero_width() {
+    ModelBuilder::new()
+        .with_total_variation(TotalVariationSolver::with_parameters(
+            SignalShape::Image { width: 0 },
+            10,
+            0.0,
+        ))
+        .build(5, 10);
+}
@@ -0,0 +1,113 @@
+use sense_motive::{
+    algorithm::{
+        Algorithm, ModelBasedCoSaMPSolver, ModelBasedIterativeHardThresholdingSolver, WaveletTree,
+    },
+    signal_utils::relative_error_l2,
+    ModelBuilder, Transformation,
+};
+
+const N: usize = 256; // original length
+const SEED: u64 = 42;
+
+const TOL_ERR: f64 = 1e-6;
+
+// piecewise constant, its Haar coefficients are nonzero along the paths to the jumps only
+fn generate_steps() -> Vec<f64> {
+    (0..N)
+        .map(|i| match i {
+            0..=69 => 1.0,
+            70..=179 => -0.5,
+            _ => 2.0,
+        })
+        .collect()
+}
+
+// 16x16 image with a bright rectangle
+fn generate_image() -> Vec<f64> {
+    let width = 16;
+    (0..N)
+        .map(|i| {
+            let (row, col) = (i / width, i % width);
+            if (3..9).contains(&row) && (5..14).contains(&col) {
+                1.0
+            } else {
+                0.2
+            }
+        })
+        .collect()
+}
+
+fn reconstruction_error(
+    original: &[f64],
+    transformation: Transformation,
+    algorithm: Algorithm,
+    size_compressed: usize,
+) -> f64 {
+    let model = ModelBuilder::new()
+        .with_transformation(transformation)
+        .with_algorithm(algorithm)
+        .with_seed(SEED)
+        .build(size_compressed, N);
+
+    let compressed = model.compress(original);
+    let decompressed = model.decompress(&compressed);
+    relative_error_l2(original, &decompressed)
+}
+
+#[test]
+fn cosamp_1d() {
+    let algorithm = Algorithm::ModelBasedCoSaMP(ModelBasedCoSaMPSolver::with_parameters(
+        WaveletTree::Binary,
+        17,
+        100,
+        1e-9,
+    ));
+    let error = reconstruction_error(
+        &generate_steps(),
+        Transformation::Haar1dInverse,
+        algorithm,
+        64,
+    );
+    assert!(error < TOL_ERR);
+}
+
+#[test]
+fn iht_1d() {
+    let algorithm = Algorithm::ModelBasedIterativeHardThresholding(
+        ModelBasedIterativeHardThresholdingSolver::with_parameters(
+            WaveletTree::Binary,
+            17,
+            200,
+            1e-9,
+        ),
+    );
+    let error = reconstruction_error(
+        &generate_steps(),
+        Transformation::Haar1dInverse,
+        algorithm,
+        80,
+    );
+    assert!(error < TOL_ERR);
+}
+
+#[test]
+fn cosamp_2d() {
+    let image = generate_image();
+    let support = (&Transformation::Haar2d.into_matrix(N) * image.as_slice())
+        .iter()
+        .filter(|e| e.abs() > 1e-12)
+        .count();
+    let algorithm = Algorithm::ModelBasedCoSaMP(ModelBasedCoSaMPSolver::with_parameters(
+        WaveletTree::Quad,
+        support,
+        100,
+        1e-9,
+    ));
+    let error = reconstruction_error(
+        &image,
+        Transformation::Haar2dInverse,
+        algorithm,
+        3 * support,
+    );
+    assert!(error < TOL_ERR);
+}